@@ -57,6 +57,7 @@ fn spawn_players(mut commands: Commands) {
             parent.spawn_bundle(RaycastBundle {
                 raycast: Raycast::new()
                     .with_direction(Vec2::Y * -4.0)
+                    .with_stop_at_first(true)
                     .with_offset(Vec2::new((player_size.x - 1.0) / 2.0, -player_size.y / 2.0)),
                 ..default()
             })
@@ -66,6 +67,7 @@ fn spawn_players(mut commands: Commands) {
             parent.spawn_bundle(RaycastBundle {
                 raycast: Raycast::new()
                     .with_direction(Vec2::Y * -4.0)
+                    .with_stop_at_first(true)
                     .with_offset(Vec2::new(-(player_size.x - 1.0) / 2.0, -player_size.y / 2.0)),
                 ..default()
             })
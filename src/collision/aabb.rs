@@ -1,5 +1,5 @@
 use bevy::prelude::{ Vec2, GlobalTransform };
-use crate::{components::Rectangle, collision::{ Hit, Raycast, Ray }, utils::EPSILON };
+use crate::{ components::Rectangle, collision::{ Hit, Ray } };
 
 #[derive(Copy, Clone, Debug)]
 pub struct Aabb {
@@ -21,6 +21,10 @@ impl Aabb {
         }
     }
 
+    pub fn from_min_max(min: Vec2, max: Vec2) -> Self {
+        Self::new((max - min) / 2.0, (min + max) / 2.0)
+    }
+
     pub fn extents(&self) -> Vec2 {
         self.extents
     }
@@ -38,18 +42,11 @@ impl Aabb {
     }
 
     pub fn from_rectangle(rectangle: Rectangle, transform: &GlobalTransform) -> Self {
-        Self::new(rectangle.size() / 2.0, Vec2::new(transform.translation.x, transform.translation.y))
-    }
+        let scale = Vec2::new(transform.scale.x, transform.scale.y);
 
-    pub fn from_ray(ray: &Raycast, transform: &GlobalTransform) -> Self {
-        let half_dir = ray.direction / 2.0;
         Self::new(
-            // temporary solution to ray not detecting when it checks a gap
-            // TODO remove this when QuadTree is implemented
-            half_dir.abs().max(Vec2::splat(EPSILON)),
-            Vec2::new(
-                transform.translation.x, transform.translation.y
-            ) + half_dir + ray.offset,
+            rectangle.size() * scale.abs() / 2.0,
+            Vec2::new(transform.translation.x, transform.translation.y) + rectangle.center() * scale,
         )
     }
 
@@ -67,6 +64,21 @@ impl Aabb {
         && self.max.y > other.min.y
     }
 
+    pub fn contains(&self, other: &Aabb) -> bool {
+        self.min.x <= other.min.x
+        && self.min.y <= other.min.y
+        && self.max.x >= other.max.x
+        && self.max.y >= other.max.y
+    }
+
+    pub fn merge(&self, other: &Aabb) -> Self {
+        Aabb::from_min_max(self.min.min(other.min), self.max.max(other.max))
+    }
+
+    pub fn perimeter(&self) -> f32 {
+        4.0 * (self.extents.x + self.extents.y)
+    }
+
     pub fn get_broad(&self, motion: Vec2) -> Self {
         let half_motion = motion / 2.0;
 
@@ -98,4 +110,15 @@ mod tests {
         
         assert!(a.is_overlapping(&b));
     }
+
+    #[test]
+    fn test_relative_sweep() {
+        let a = Aabb::new(Vec2::splat(1.0), Vec2::ZERO);
+        let b = Aabb::new(Vec2::splat(1.0), Vec2::new(6.0, 0.0));
+
+        let hit = a.sweep_test(&b, Vec2::new(4.0, 0.0) - Vec2::new(-4.0, 0.0)).unwrap();
+
+        assert_eq!(hit.time, 0.5);
+        assert_eq!(hit.normal, Vec2::new(-1.0, 0.0));
+    }
 }
\ No newline at end of file
@@ -0,0 +1,155 @@
+use bevy::prelude::Vec2;
+use crate::collision::{ Aabb, Hit, Polygon };
+
+/// Circle in world space.
+#[derive(Clone, Copy, Debug)]
+pub struct Ball {
+    center: Vec2,
+    radius: f32,
+}
+
+impl Ball {
+    pub fn new(center: Vec2, radius: f32) -> Self {
+        Self {
+            center,
+            radius,
+        }
+    }
+
+    pub fn center(&self) -> Vec2 {
+        self.center
+    }
+
+    pub fn radius(&self) -> f32 {
+        self.radius
+    }
+
+    pub fn aabb(&self) -> Aabb {
+        Aabb::new(Vec2::splat(self.radius), self.center)
+    }
+
+    pub fn is_overlapping(&self, other: &Ball) -> bool {
+        self.center.distance_squared(other.center) < (self.radius + other.radius).powi(2)
+    }
+
+    pub fn is_overlapping_polygon(&self, other: &Polygon) -> bool {
+        let (closest, is_inside) = other.closest_point(self.center);
+        is_inside || closest.distance_squared(self.center) < self.radius.powi(2)
+    }
+
+    pub fn sweep_test(&self, other: &Ball, motion: Vec2) -> Option<Hit> {
+        let radius = self.radius + other.radius;
+        if self.is_overlapping(other) {
+            return Some(Hit { time: 0.0, normal: (self.center - other.center).normalize_or_zero() });
+        }
+
+        let time = sweep_point_circle(self.center, motion, other.center, radius)?;
+        Some(Hit {
+            time,
+            normal: (self.center + motion * time - other.center).normalize_or_zero(),
+        })
+    }
+
+    pub fn sweep_aabb(&self, other: &Aabb, motion: Vec2) -> Option<Hit> {
+        self.sweep_polygon(&Polygon::from_aabb(other), motion)
+    }
+
+    /// Sweeps the center against `other` grown by the radius,
+    /// which is its edges pushed out plus a circle on every vertex.
+    pub fn sweep_polygon(&self, other: &Polygon, motion: Vec2) -> Option<Hit> {
+        if self.is_overlapping_polygon(other) {
+            let (closest, is_inside) = other.closest_point(self.center);
+            let normal = match is_inside {
+                true => other.edges()
+                    .max_by(|a, b| (self.center - a.0).dot(a.2).partial_cmp(&(self.center - b.0).dot(b.2)).unwrap())
+                    .map_or(Vec2::ZERO, |(_, _, normal)| normal),
+                false => (self.center - closest).normalize_or_zero(),
+            };
+            return Some(Hit { time: 0.0, normal });
+        }
+
+        let mut best: Option<Hit> = None;
+        let mut keep = |time: f32, normal: Vec2| {
+            if best.is_none_or(|hit| time < hit.time) {
+                best = Some(Hit { time, normal });
+            }
+        };
+
+        for (a, b, normal) in other.edges() {
+            if motion.dot(normal) >= 0.0 { continue; }
+
+            let offset = normal * self.radius;
+            if let Some(time) = sweep_point_segment(self.center, motion, a + offset, b + offset) {
+                keep(time, normal);
+            }
+        }
+
+        for &vertex in other.vertices() {
+            if let Some(time) = sweep_point_circle(self.center, motion, vertex, self.radius) {
+                keep(time, (self.center + motion * time - vertex).normalize_or_zero());
+            }
+        }
+
+        best
+    }
+}
+
+/// Time at which `point` moving by `motion` enters the circle, if it does before the end.
+pub fn sweep_point_circle(point: Vec2, motion: Vec2, center: Vec2, radius: f32) -> Option<f32> {
+    let offset = point - center;
+    let a = motion.length_squared();
+    let b = 2.0 * offset.dot(motion);
+    let c = offset.length_squared() - radius * radius;
+
+    if a == 0.0 || b >= 0.0 { return None; }
+
+    let discriminant = b * b - 4.0 * a * c;
+    if discriminant < 0.0 { return None; }
+
+    let time = (-b - discriminant.sqrt()) / (2.0 * a);
+    if (0.0..1.0).contains(&time) { Some(time) } else { None }
+}
+
+/// Time at which `point` moving by `motion` crosses the segment `a`-`b`.
+pub fn sweep_point_segment(point: Vec2, motion: Vec2, a: Vec2, b: Vec2) -> Option<f32> {
+    let edge = b - a;
+    let denominator = motion.perp_dot(edge);
+    if denominator == 0.0 { return None; }
+
+    let time = (a - point).perp_dot(edge) / denominator;
+    let along = (a - point).perp_dot(motion) / denominator;
+
+    if (0.0..1.0).contains(&time) && (0.0..=1.0).contains(&along) { Some(time) } else { None }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::collision::{ Aabb, Ball };
+    use bevy::math::Vec2;
+
+    #[test]
+    fn test_sweep_ball() {
+        let a = Ball::new(Vec2::ZERO, 1.0);
+        let b = Ball::new(Vec2::new(6.0, 0.0), 1.0);
+
+        let hit = a.sweep_test(&b, Vec2::new(8.0, 0.0)).unwrap();
+
+        assert_eq!(hit.time, 0.5);
+        assert_eq!(hit.normal, Vec2::new(-1.0, 0.0));
+    }
+
+    #[test]
+    fn test_sweep_aabb() {
+        let a = Ball::new(Vec2::new(0.0, 4.0), 1.0);
+        let b = Aabb::new(Vec2::splat(1.0), Vec2::ZERO);
+
+        let hit = a.sweep_aabb(&b, Vec2::new(0.0, -4.0)).unwrap();
+        assert_eq!(hit.time, 0.5);
+        assert_eq!(hit.normal, Vec2::new(0.0, 1.0));
+
+        // passing the corner, the ball rounds it off instead of hitting a face
+        let c = Ball::new(Vec2::new(1.8, 4.0), 1.0);
+        let hit = c.sweep_aabb(&b, Vec2::new(0.0, -4.0)).unwrap();
+        assert!(hit.normal.x > 0.0 && hit.normal.y > 0.0);
+    }
+}
@@ -0,0 +1,248 @@
+use bevy::prelude::{ GlobalTransform, Quat, Vec2 };
+use crate::{ collision::{ Aabb, Ball, Hit, Obb, Polygon, Stadium }, components::{ RotationMode, Shape } };
+
+/// A `Shape` placed in world space.
+#[derive(Clone, Debug)]
+pub enum Collider {
+    Aabb(Aabb),
+    Obb(Obb),
+    Polygon(Polygon),
+    Ball(Ball),
+    Stadium(Stadium),
+    Compound(Vec<Collider>),
+}
+
+impl Collider {
+    /// Places `shape` with the translation and scale of `transform`,
+    /// applying its rotation as `rotation_mode` says.
+    pub fn from_shape(shape: &Shape, transform: &GlobalTransform, rotation_mode: RotationMode) -> Self {
+        let position = Vec2::new(transform.translation.x, transform.translation.y);
+        let scale = Vec2::new(transform.scale.x, transform.scale.y);
+        let rotation = match rotation_mode {
+            RotationMode::Ignore => Quat::IDENTITY,
+            _ => transform.rotation,
+        };
+        let is_rotated = rotation != Quat::IDENTITY;
+        let place = |point: Vec2| position + (rotation * (point * scale).extend(0.0)).truncate();
+
+        let collider = match shape {
+            Shape::Rectangle(rectangle) if !is_rotated => Self::Aabb(Aabb::from_rectangle(*rectangle, transform)),
+            Shape::Rectangle(rectangle) => Self::Polygon(Polygon::new(
+                Polygon::from_aabb(&Aabb::new(rectangle.size() / 2.0, rectangle.center()))
+                    .vertices().iter().map(|&v| place(v)).collect(),
+            )),
+            Shape::Slope(slope) => Self::Polygon(Polygon::new(
+                slope.vertices().iter().map(|&v| place(v)).collect(),
+            )),
+            Shape::Polygon(polygon) => Self::Polygon(Polygon::new(
+                polygon.vertices().iter().map(|&v| place(v)).collect(),
+            )),
+            Shape::OrientedBox(oriented_box) => {
+                // the scale stretches the box along its own axes, which keeps it a box
+                // for a uniform scale or a box lined up with the transform, and only
+                // approximates the skewed parallelogram otherwise
+                let x_axis = oriented_box.axis() * scale;
+                let y_axis = oriented_box.axis().perp() * scale;
+
+                Self::Obb(Obb::new(
+                    oriented_box.size() * Vec2::new(x_axis.length(), y_axis.length()) / 2.0,
+                    position,
+                    (rotation * x_axis.normalize_or_zero().extend(0.0)).truncate(),
+                ))
+            },
+            Shape::Circle(circle) => Self::Ball(Ball::new(position, circle.radius() * scale.abs().max_element())),
+            Shape::Capsule(capsule) => Self::Stadium(Stadium::new(
+                position,
+                place(capsule.half_segment()) - position,
+                capsule.radius() * scale.abs().max_element(),
+            )),
+            Shape::Compound(compound) => Self::Compound(compound.shapes().iter()
+                .map(|(offset, shape)| {
+                    let mut transform = *transform;
+                    transform.translation = place(*offset).extend(transform.translation.z);
+                    Self::from_shape(shape, &transform, rotation_mode)
+                })
+                .collect()),
+        };
+
+        match (rotation_mode, &collider) {
+            (RotationMode::Enclose, Self::Polygon(_) | Self::Stadium(_)) if is_rotated => Self::Aabb(collider.aabb()),
+            _ => collider,
+        }
+    }
+
+    pub fn aabb(&self) -> Aabb {
+        match self {
+            Self::Aabb(aabb) => *aabb,
+            Self::Obb(obb) => obb.aabb(),
+            Self::Polygon(polygon) => polygon.aabb(),
+            Self::Ball(ball) => ball.aabb(),
+            Self::Stadium(stadium) => stadium.aabb(),
+            Self::Compound(parts) => parts.iter()
+                .filter(|part| !part.is_empty())
+                .map(|part| part.aabb())
+                .reduce(|a, b| a.merge(&b))
+                .unwrap_or_else(|| Aabb::new(Vec2::ZERO, Vec2::ZERO)),
+        }
+    }
+
+    pub fn translated(&self, offset: Vec2) -> Self {
+        match self {
+            Self::Aabb(aabb) => Self::Aabb(Aabb::new(aabb.extents(), aabb.position() + offset)),
+            Self::Obb(obb) => Self::Obb(Obb::new(obb.extents(), obb.position() + offset, obb.axes()[0])),
+            Self::Polygon(polygon) => Self::Polygon(Polygon::new(
+                polygon.vertices().iter().map(|&v| v + offset).collect(),
+            )),
+            Self::Ball(ball) => Self::Ball(Ball::new(ball.center() + offset, ball.radius())),
+            Self::Stadium(stadium) => Self::Stadium(Stadium::new(stadium.center() + offset, stadium.half_segment(), stadium.radius())),
+            Self::Compound(parts) => Self::Compound(parts.iter().map(|part| part.translated(offset)).collect()),
+        }
+    }
+
+    fn to_polygon(&self) -> Option<Polygon> {
+        match self {
+            Self::Aabb(aabb) => Some(Polygon::from_aabb(aabb)),
+            Self::Obb(obb) => Some(Polygon::new(obb.vertices().to_vec())),
+            Self::Polygon(polygon) => Some(polygon.clone()),
+            Self::Ball(_) | Self::Stadium(_) | Self::Compound(_) => None,
+        }
+    }
+
+    /// Inner points and radius, the collider being their hull grown by the radius.
+    fn core(&self) -> (Vec<Vec2>, f32) {
+        match self {
+            Self::Aabb(aabb) => (Polygon::from_aabb(aabb).vertices().to_vec(), 0.0),
+            Self::Obb(obb) => (obb.vertices().to_vec(), 0.0),
+            Self::Polygon(polygon) => (polygon.vertices().to_vec(), 0.0),
+            Self::Ball(ball) => (vec![ball.center()], ball.radius()),
+            Self::Stadium(stadium) => (stadium.segment().to_vec(), stadium.radius()),
+            Self::Compound(_) => unreachable!("compounds are split into their parts first"),
+        }
+    }
+
+    /// Minkowski difference of `other` and `self` as a ball at the origin and
+    /// the polygon it collides with, so sweeping `self` is sweeping that ball.
+    fn minkowski_diff(&self, other: &Collider) -> (Ball, Polygon) {
+        let (a, a_radius) = self.core();
+        let (b, b_radius) = other.core();
+        let points = b.iter().flat_map(|&b| a.iter().map(move |&a| b - a)).collect();
+
+        (Ball::new(Vec2::ZERO, a_radius + b_radius), Polygon::convex_hull(points))
+    }
+
+    pub fn is_overlapping(&self, other: &Collider) -> bool {
+        if self.is_empty() || other.is_empty() { return false; }
+        if !self.aabb().is_overlapping(&other.aabb()) { return false; }
+
+        match (self, other) {
+            (Self::Compound(parts), other) | (other, Self::Compound(parts)) => parts.iter().any(|part| part.is_overlapping(other)),
+            (Self::Aabb(_), Self::Aabb(_)) => true,
+            (Self::Obb(a), Self::Obb(b)) => a.is_overlapping(b),
+            (Self::Obb(a), Self::Aabb(b)) | (Self::Aabb(b), Self::Obb(a)) => a.is_overlapping(&Obb::from_aabb(b)),
+            (Self::Stadium(_), _) | (_, Self::Stadium(_)) => {
+                let (ball, polygon) = self.minkowski_diff(other);
+                ball.is_overlapping_polygon(&polygon)
+            },
+            (Self::Ball(a), Self::Ball(b)) => a.is_overlapping(b),
+            (Self::Ball(a), b) | (b, Self::Ball(a)) => b.to_polygon().is_some_and(|b| a.is_overlapping_polygon(&b)),
+            (a, b) => match (a.to_polygon(), b.to_polygon()) {
+                (Some(a), Some(b)) => a.is_overlapping(&b),
+                _ => false,
+            },
+        }
+    }
+
+    /// Time of impact of `self` moving by `motion` against `other`.
+    pub fn sweep_test(&self, other: &Collider, motion: Vec2) -> Option<Hit> {
+        if motion == Vec2::ZERO || self.is_empty() || other.is_empty() { return None };
+
+        match (self, other) {
+            (Self::Compound(_), _) | (_, Self::Compound(_)) => self.sweep_parts(other, motion).map(|(hit, _, _)| hit),
+            (Self::Aabb(a), Self::Aabb(b)) => a.sweep_test(b, motion),
+            (Self::Obb(a), Self::Aabb(b)) => a.sweep_aabb(b, motion),
+            (Self::Stadium(_), _) | (_, Self::Stadium(_)) => {
+                let (ball, polygon) = self.minkowski_diff(other);
+                ball.sweep_polygon(&polygon, motion)
+            },
+            (Self::Ball(a), Self::Ball(b)) => a.sweep_test(b, motion),
+            (Self::Ball(a), b) => a.sweep_polygon(&b.to_polygon()?, motion),
+            // sweep the ball the other way and flip the normal back
+            (a, Self::Ball(b)) => b.sweep_polygon(&a.to_polygon()?, -motion)
+                .map(|hit| Hit { time: hit.time, normal: -hit.normal }),
+            (a, b) => a.to_polygon()?.sweep_test(&b.to_polygon()?, motion),
+        }
+    }
+
+    /// Like `sweep_test`, also giving the index of the part of `self`
+    /// and of `other` that were hit, which is 0 for anything but compounds.
+    pub fn sweep_parts(&self, other: &Collider, motion: Vec2) -> Option<(Hit, usize, usize)> {
+        match (self, other) {
+            (Self::Compound(parts), _) => earliest(parts.iter().enumerate()
+                .filter_map(|(i, part)| part.sweep_parts(other, motion).map(|(hit, _, j)| (hit, i, j)))),
+            (_, Self::Compound(parts)) => earliest(parts.iter().enumerate()
+                .filter_map(|(j, part)| self.sweep_parts(part, motion).map(|(hit, i, _)| (hit, i, j)))),
+            _ => self.sweep_test(other, motion).map(|hit| (hit, 0, 0)),
+        }
+    }
+
+    /// Whether this is an empty polygon, which has nothing to collide with.
+    fn is_empty(&self) -> bool {
+        matches!(self, Self::Polygon(polygon) if polygon.vertices().is_empty())
+    }
+}
+
+/// Earliest of `hits`, preferring the ones with a normal on ties.
+fn earliest(hits: impl Iterator<Item = (Hit, usize, usize)>) -> Option<(Hit, usize, usize)> {
+    hits.min_by(|(a, _, _), (b, _, _)| a.time.partial_cmp(&b.time).unwrap()
+        .then((a.normal == Vec2::ZERO).cmp(&(b.normal == Vec2::ZERO))))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::collision::Collider;
+    use crate::components::{ ConvexPolygon, OrientedBox, Rectangle, RotationMode, Shape };
+    use bevy::prelude::{ GlobalTransform, Quat, Vec2, Vec3 };
+
+    #[test]
+    fn test_from_shape() {
+        let shape = Shape::from(Rectangle::new().with_size(Vec2::new(4.0, 2.0)));
+        let transform = GlobalTransform::from_xyz(10.0, 0.0, 0.0)
+            .with_scale(Vec3::new(2.0, -1.0, 1.0))
+            .with_rotation(Quat::from_rotation_z(std::f32::consts::FRAC_PI_4));
+
+        let a = Collider::from_shape(&shape, &transform, RotationMode::Ignore);
+        assert!(matches!(a, Collider::Aabb(_)));
+        assert_eq!(a.aabb().max(), Vec2::new(14.0, 1.0));
+
+        // the scaled box is 8 by 2, so 5 * sqrt(2) by 5 * sqrt(2) once turned
+        let b = Collider::from_shape(&shape, &transform, RotationMode::Enclose);
+        assert!(matches!(b, Collider::Aabb(_)));
+        assert!((b.aabb().extents() - Vec2::splat(5.0 / 2f32.sqrt())).length() < 0.0001);
+
+        let c = Collider::from_shape(&shape, &transform, RotationMode::Exact);
+        assert!(matches!(c, Collider::Polygon(_)));
+        assert!((c.aabb().extents() - b.aabb().extents()).length() < 0.0001);
+    }
+
+    #[test]
+    fn test_scaled_oriented_box() {
+        // a box standing upright, stretched 3 times along the x axis of the body
+        let shape = Shape::from(OrientedBox::new().with_size(Vec2::new(4.0, 2.0)).with_angle(std::f32::consts::FRAC_PI_2));
+        let transform = GlobalTransform::from_scale(Vec3::new(3.0, 1.0, 1.0));
+
+        let a = Collider::from_shape(&shape, &transform, RotationMode::Exact);
+        assert!((a.aabb().extents() - Vec2::new(3.0, 2.0)).length() < 0.0001);
+    }
+
+    #[test]
+    fn test_empty_polygon() {
+        let transform = GlobalTransform::from_xyz(0.0, 0.0, 0.0);
+        let a = Collider::from_shape(&Shape::from(ConvexPolygon::new()), &transform, RotationMode::Exact);
+        let b = Collider::from_shape(&Shape::from(Rectangle::new()), &transform, RotationMode::Exact);
+
+        assert!(a.aabb().extents().is_finite());
+        assert!(a.sweep_test(&b, Vec2::X).is_none());
+        assert!(b.sweep_test(&a, Vec2::X).is_none());
+        assert!(!a.is_overlapping(&b));
+    }
+}
@@ -4,6 +4,8 @@ use bevy::prelude::{ Entity, Vec2 };
 pub struct Contact {
     pub(crate) entity: Entity,
     pub(crate) normal: Vec2,
+    pub(crate) shape: usize,
+    pub(crate) own_shape: usize,
 }
 
 impl Contact {
@@ -14,4 +16,14 @@ impl Contact {
     pub fn normal(&self) -> Vec2 {
         self.normal
     }
+
+    /// Index of the touched shape of `entity` if it's a `Compound`, 0 otherwise.
+    pub fn shape(&self) -> usize {
+        self.shape
+    }
+
+    /// Index of the shape of this body that touched `entity`.
+    pub fn own_shape(&self) -> usize {
+        self.own_shape
+    }
 }
\ No newline at end of file
@@ -0,0 +1,93 @@
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use crate::components::KinematicBody;
+
+/// Sent the first frame `entity` gets a `Contact` with `other`.
+///
+/// Events are sent from the point of view of each `KinematicBody`, so two
+/// kinematics hitting each other send one event each.
+pub struct CollisionStarted {
+    pub entity: Entity,
+    pub other: Entity,
+    pub normal: Vec2,
+}
+
+/// Sent the first frame `entity` no longer has a `Contact` with `other`.
+pub struct CollisionEnded {
+    pub entity: Entity,
+    pub other: Entity,
+}
+
+/// Contacts of the previous frame, keyed by `(entity, other)`.
+#[derive(Default)]
+pub struct ContactPairs {
+    pairs: HashMap<(Entity, Entity), Vec2>,
+}
+
+impl ContactPairs {
+    pub fn get(&self, entity: Entity, other: Entity) -> Option<Vec2> {
+        self.pairs.get(&(entity, other)).copied()
+    }
+}
+
+pub fn contact_events(
+    kinematics: Query<(Entity, &KinematicBody)>,
+
+    mut contact_pairs: ResMut<ContactPairs>,
+    mut ev_started: EventWriter<CollisionStarted>,
+    mut ev_ended: EventWriter<CollisionEnded>,
+) {
+    let mut pairs = HashMap::default();
+    for (entity, body) in kinematics.iter() {
+        for contact in body.contacts.iter() {
+            pairs.insert((entity, contact.entity), contact.normal);
+        }
+    }
+
+    for (&(entity, other), &normal) in pairs.iter() {
+        if !contact_pairs.pairs.contains_key(&(entity, other)) {
+            ev_started.send(CollisionStarted { entity, other, normal });
+        }
+    }
+
+    for &(entity, other) in contact_pairs.pairs.keys() {
+        if !pairs.contains_key(&(entity, other)) {
+            ev_ended.send(CollisionEnded { entity, other });
+        }
+    }
+
+    contact_pairs.pairs = pairs;
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::event::Events;
+    use bevy::ecs::system::SystemState;
+    use bevy::prelude::*;
+    use crate::collision::{ contact_events, CollisionEnded, CollisionStarted, Contact, ContactPairs };
+    use crate::components::{ KinematicBody, Rectangle };
+
+    #[test]
+    fn test_started_ended() {
+        let mut world = World::new();
+        world.init_resource::<ContactPairs>();
+        world.init_resource::<Events<CollisionStarted>>();
+        world.init_resource::<Events<CollisionEnded>>();
+
+        let body = world.spawn().insert(KinematicBody::new(Rectangle::new().with_size(Vec2::splat(2.0)))).id();
+        let other = world.spawn().id();
+
+        let mut stage = SystemStage::single_threaded().with_system(contact_events);
+        let mut events: SystemState<(EventReader<CollisionStarted>, EventReader<CollisionEnded>)> = SystemState::new(&mut world);
+
+        // the contact keeps going for a frame with another normal before ending
+        for (normal, started, ended) in [(None, 0, 0), (Some(Vec2::Y), 1, 0), (Some(Vec2::X), 0, 0), (None, 0, 1), (None, 0, 0)] {
+            world.get_mut::<KinematicBody>(body).unwrap().contacts = normal.map(|normal| Contact { entity: other, normal, shape: 0, own_shape: 0 }).into_iter().collect();
+            stage.run(&mut world);
+
+            let (mut ev_started, mut ev_ended) = events.get_mut(&mut world);
+            assert_eq!(ev_started.iter().map(|ev| (ev.entity, ev.other)).collect::<Vec<_>>(), vec![(body, other); started]);
+            assert_eq!(ev_ended.iter().map(|ev| (ev.entity, ev.other)).collect::<Vec<_>>(), vec![(body, other); ended]);
+        }
+    }
+}
@@ -0,0 +1,182 @@
+use bevy::prelude::{ Entity, IVec2, Vec2 };
+use bevy::utils::{ HashMap, HashSet };
+use crate::collision::{ Aabb, Broadphase, Ray };
+
+pub const DEFAULT_CELL_SIZE: f32 = 64.0;
+
+/// Uniform grid that buckets every body by the cells its `Aabb` covers.
+pub struct SpatialGrid {
+    cell_size: f32,
+
+    cells: HashMap<IVec2, Vec<Entity>>,
+    entries: HashMap<Entity, (Aabb, IVec2, IVec2)>,
+}
+
+impl Default for SpatialGrid {
+    fn default() -> Self {
+        Self::new(DEFAULT_CELL_SIZE)
+    }
+}
+
+impl SpatialGrid {
+    pub fn new(cell_size: f32) -> Self {
+        Self {
+            cell_size: cell_size.abs().max(f32::EPSILON),
+
+            cells: HashMap::default(),
+            entries: HashMap::default(),
+        }
+    }
+
+    pub fn cell_size(&self) -> f32 {
+        self.cell_size
+    }
+
+    fn cell(&self, point: Vec2) -> IVec2 {
+        (point / self.cell_size).floor().as_ivec2()
+    }
+
+    // http://www.cse.yorku.ca/~amana/research/grid.pdf
+    fn traverse(&self, ray: &Ray) -> Vec<IVec2> {
+        let mut cell = self.cell(ray.position);
+        let last = self.cell(ray.position + ray.direction);
+        let step = ray.direction.signum().as_ivec2();
+
+        let next = (cell + step.max(IVec2::ZERO)).as_vec2() * self.cell_size;
+        let mut t_max = Vec2::new(
+            if ray.direction.x != 0.0 { (next.x - ray.position.x) / ray.direction.x } else { f32::INFINITY },
+            if ray.direction.y != 0.0 { (next.y - ray.position.y) / ray.direction.y } else { f32::INFINITY },
+        );
+        let t_delta = Vec2::splat(self.cell_size) / ray.direction.abs();
+
+        let mut cells = vec![cell];
+        let max_steps = (last - cell).abs();
+        for _ in 0..(max_steps.x + max_steps.y) {
+            if t_max.x < t_max.y {
+                cell.x += step.x;
+                t_max.x += t_delta.x;
+            } else {
+                cell.y += step.y;
+                t_max.y += t_delta.y;
+            }
+            cells.push(cell);
+        }
+
+        cells
+    }
+}
+
+impl Broadphase for SpatialGrid {
+    fn get(&self, entity: Entity) -> Option<Aabb> {
+        self.entries.get(&entity).map(|(aabb, _, _)| *aabb)
+    }
+
+    fn insert(&mut self, entity: Entity, aabb: Aabb) {
+        let min = self.cell(aabb.min());
+        let max = self.cell(aabb.max());
+
+        if let Some((old, old_min, old_max)) = self.entries.get_mut(&entity) {
+            if *old_min == min && *old_max == max {
+                *old = aabb;
+                return;
+            }
+        }
+
+        self.remove(entity);
+        for x in min.x..=max.x {
+            for y in min.y..=max.y {
+                self.cells.entry(IVec2::new(x, y)).or_default().push(entity);
+            }
+        }
+        self.entries.insert(entity, (aabb, min, max));
+    }
+
+    fn remove(&mut self, entity: Entity) {
+        let (_, min, max) = match self.entries.remove(&entity) {
+            Some(entry) => entry,
+            None => return,
+        };
+
+        for x in min.x..=max.x {
+            for y in min.y..=max.y {
+                let cell = IVec2::new(x, y);
+                if let Some(entities) = self.cells.get_mut(&cell) {
+                    entities.retain(|&e| e != entity);
+                    if entities.is_empty() { self.cells.remove(&cell); }
+                }
+            }
+        }
+    }
+
+    fn query_aabb(&self, aabb: &Aabb) -> Vec<Entity> {
+        let min = self.cell(aabb.min());
+        let max = self.cell(aabb.max());
+
+        let mut visited = HashSet::default();
+        let mut result = Vec::new();
+        for x in min.x..=max.x {
+            for y in min.y..=max.y {
+                for &entity in self.cells.get(&IVec2::new(x, y)).into_iter().flatten() {
+                    if !visited.insert(entity) { continue; }
+
+                    if self.entries[&entity].0.is_overlapping(aabb) {
+                        result.push(entity);
+                    }
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Entities stored in the cells crossed by `ray`, in traversal order.
+    fn query_ray(&self, ray: &Ray) -> Vec<Entity> {
+        let mut visited = HashSet::default();
+        let mut result = Vec::new();
+
+        for cell in self.traverse(ray) {
+            for &entity in self.cells.get(&cell).into_iter().flatten() {
+                if visited.insert(entity) {
+                    result.push(entity);
+                }
+            }
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::collision::{ Aabb, Broadphase, Ray, SpatialGrid };
+    use bevy::prelude::{ Entity, Vec2 };
+
+    #[test]
+    fn test_query_aabb() {
+        let mut grid = SpatialGrid::new(10.0);
+        let a = Entity::from_raw(0);
+        let b = Entity::from_raw(1);
+
+        grid.insert(a, Aabb::new(Vec2::splat(5.0), Vec2::new(5.0, 5.0)));
+        grid.insert(b, Aabb::new(Vec2::splat(5.0), Vec2::new(45.0, 5.0)));
+
+        assert_eq!(grid.query_aabb(&Aabb::new(Vec2::splat(2.0), Vec2::new(8.0, 2.0))), vec![a]);
+
+        grid.insert(b, Aabb::new(Vec2::splat(5.0), Vec2::new(12.0, 5.0)));
+        assert_eq!(grid.query_aabb(&Aabb::new(Vec2::splat(2.0), Vec2::new(10.0, 2.0))).len(), 2);
+
+        grid.remove(a);
+        assert_eq!(grid.query_aabb(&Aabb::new(Vec2::splat(2.0), Vec2::new(8.0, 2.0))), vec![b]);
+    }
+
+    #[test]
+    fn test_query_ray() {
+        let mut grid = SpatialGrid::new(10.0);
+        let a = Entity::from_raw(0);
+
+        grid.insert(a, Aabb::new(Vec2::splat(5.0), Vec2::new(35.0, 25.0)));
+
+        assert_eq!(grid.query_ray(&Ray::new(Vec2::new(40.0, 30.0), Vec2::ZERO)), vec![a]);
+        assert!(grid.query_ray(&Ray::new(Vec2::new(40.0, 0.0), Vec2::ZERO)).is_empty());
+    }
+}
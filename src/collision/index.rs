@@ -0,0 +1,121 @@
+use std::ops::{ Deref, DerefMut };
+use bevy::prelude::Entity;
+use bevy::utils::HashMap;
+use crate::collision::{ Aabb, AabbTree, Ray, SpatialGrid, SweepAndPrune };
+
+/// Acceleration structure used to find the bodies a box or a ray may touch.
+///
+/// Statics are stored with their own box, kinematics with the box
+/// swept by their motion.
+pub trait Broadphase: Send + Sync + 'static {
+    fn get(&self, entity: Entity) -> Option<Aabb>;
+
+    /// Inserts `entity`, or updates it if it's already stored.
+    fn insert(&mut self, entity: Entity, aabb: Aabb);
+
+    fn remove(&mut self, entity: Entity);
+
+    /// Entities whose `Aabb` overlaps `aabb`.
+    fn query_aabb(&self, aabb: &Aabb) -> Vec<Entity>;
+
+    /// Entities whose `Aabb` may be touched by `ray`.
+    fn query_ray(&self, ray: &Ray) -> Vec<Entity>;
+
+    /// Called once per frame after every body has been inserted or removed.
+    fn update(&mut self) {}
+
+    /// Pairs of overlapping boxes, where the first entity is one of `entities`.
+    fn query_pairs(&self, entities: &[Entity]) -> Vec<(Entity, Entity)> {
+        let mut pairs = Vec::new();
+
+        for &a in entities {
+            let aabb = match self.get(a) {
+                Some(aabb) => aabb,
+                None => continue,
+            };
+
+            for b in self.query_aabb(&aabb) {
+                if a != b { pairs.push((a, b)); }
+            }
+        }
+
+        pairs
+    }
+}
+
+/// Tests every stored box, fine for scenes with few bodies.
+#[derive(Default)]
+pub struct BruteForce {
+    entries: HashMap<Entity, Aabb>,
+}
+
+impl Broadphase for BruteForce {
+    fn get(&self, entity: Entity) -> Option<Aabb> {
+        self.entries.get(&entity).copied()
+    }
+
+    fn insert(&mut self, entity: Entity, aabb: Aabb) {
+        self.entries.insert(entity, aabb);
+    }
+
+    fn remove(&mut self, entity: Entity) {
+        self.entries.remove(&entity);
+    }
+
+    fn query_aabb(&self, aabb: &Aabb) -> Vec<Entity> {
+        self.entries.iter()
+            .filter(|(_, other)| other.is_overlapping(aabb))
+            .map(|(&entity, _)| entity)
+            .collect()
+    }
+
+    fn query_ray(&self, ray: &Ray) -> Vec<Entity> {
+        self.entries.iter()
+            .filter(|(_, other)| ray.overlaps_aabb(other))
+            .map(|(&entity, _)| entity)
+            .collect()
+    }
+}
+
+/// Resource holding the `Broadphase` used by the physics systems.
+///
+/// Insert it before adding `PhysicsPlugin` to replace the default `BruteForce`.
+pub struct SpatialIndex(Box<dyn Broadphase>);
+
+impl Default for SpatialIndex {
+    fn default() -> Self {
+        Self::new(BruteForce::default())
+    }
+}
+
+impl SpatialIndex {
+    pub fn new(broadphase: impl Broadphase) -> Self {
+        Self(Box::new(broadphase))
+    }
+
+    pub fn grid(cell_size: f32) -> Self {
+        Self::new(SpatialGrid::new(cell_size))
+    }
+
+    pub fn tree(margin: f32) -> Self {
+        Self::new(AabbTree::new(margin))
+    }
+
+    pub fn sweep_and_prune() -> Self {
+        Self::new(SweepAndPrune::new())
+    }
+}
+
+impl Deref for SpatialIndex {
+    type Target = dyn Broadphase;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.as_ref()
+    }
+}
+
+impl DerefMut for SpatialIndex {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.0.as_mut()
+    }
+}
@@ -1,16 +1,60 @@
 pub mod aabb;
+pub mod ball;
+pub mod collider;
 pub mod contact;
+pub mod events;
+pub mod grid;
+pub mod index;
+pub mod obb;
+pub mod polygon;
+pub mod query;
 pub mod ray;
+pub mod sap;
+pub mod stadium;
+pub mod tree;
+pub mod trigger;
 
 use bevy::prelude::*;
+use bevy::utils::{ HashMap, HashSet };
 use crate::components::*;
-use crate::utils::slide_motion;
+use crate::utils::stop_motion;
 
 pub use aabb::*;
+pub use ball::*;
+pub use collider::*;
 pub use contact::*;
+pub use events::*;
+pub use grid::*;
+pub use index::*;
+pub use obb::*;
+pub use polygon::*;
+pub use query::*;
 pub use ray::*;
+pub use sap::*;
+pub use stadium::*;
+pub use tree::*;
+pub use trigger::*;
+
+const SLIDE_SKIN: f32 = 0.001;
+const ONE_WAY_SKIN: f32 = 0.01;
+const INTERNAL_EDGE_TOLERANCE: f32 = 0.01;
+/// Gap left between a body and the moving static pushing it out.
+const PUSH_SKIN: f32 = 0.001;
+
+/// Tuning of `solve`, insert it before adding the `PhysicsPlugin` to change it.
+pub struct SolverSettings {
+    /// Most sweeps done for a body each frame, each one sliding along
+    /// what the previous one hit.
+    pub max_slides: usize,
+}
 
-const DIAGONAL_SOLVE: Vec2 = Vec2::X;
+impl Default for SolverSettings {
+    fn default() -> Self {
+        Self {
+            max_slides: 4,
+        }
+    }
+}
 
 pub struct BroadEvent {
     pub entity: Entity,
@@ -19,6 +63,8 @@ pub struct BroadEvent {
     pub statics: Vec<Entity>,
 }
 
+/// Bodies hit by the sweep of `entity`, earliest first. `solve` starts
+/// from these statics and re-queries the world for the next slides.
 pub struct NarrowEvent {
     pub entity: Entity,
 
@@ -36,24 +82,84 @@ pub struct Collisions {
     pub entities: Vec<Entity>,
 }
 
+type ChangedStatic = Or<(Changed<StaticBody>, Changed<GlobalTransform>)>;
+type ChangedKinematic = Or<(Changed<KinematicBody>, Changed<GlobalTransform>)>;
+type Platform = (
+    Entity,
+    &'static StaticBody,
+    &'static mut Transform,
+    &'static GlobalTransform,
+    Option<&'static Parent>,
+    Option<&'static Sensor>,
+);
+
+pub fn carry_riders(
+    mut kinematics: Query<&mut KinematicBody>,
+    statics: Query<&StaticBody>,
+) {
+    for mut body in kinematics.iter_mut() {
+        // contacts are still the ones of the previous frame
+        let platform = body.contacts.iter()
+            .filter(|contact| contact.normal.y > 0.0)
+            .find_map(|contact| statics.get(contact.entity).ok())
+            .map(|platform| platform.motion);
+
+        body.carried = platform.unwrap_or(Vec2::ZERO);
+    }
+}
+
+pub fn update_index(
+    mut index: ResMut<SpatialIndex>,
+    statics: Query<(Entity, &StaticBody, &GlobalTransform), ChangedStatic>,
+    kinematics: Query<(Entity, &KinematicBody, &GlobalTransform), ChangedKinematic>,
+    removed_statics: RemovedComponents<StaticBody>,
+    removed_kinematics: RemovedComponents<KinematicBody>,
+) {
+    for entity in removed_statics.iter().chain(removed_kinematics.iter()) {
+        index.remove(entity);
+    }
+
+    for (entity, body, trans) in statics.iter() {
+        index.insert(entity, Collider::from_shape(&body.shape, trans, body.rotation_mode).aabb().get_broad(body.motion));
+    }
+
+    for (entity, body, trans) in kinematics.iter() {
+        index.insert(entity, Collider::from_shape(&body.shape, trans, body.rotation_mode).aabb().get_broad(body.total_motion()));
+    }
+
+    index.update();
+}
+
 pub fn broadphase(
-    kinematics: Query<(Entity, &KinematicBody, &GlobalTransform)>,
-    statics: Query<(Entity, &StaticBody, &GlobalTransform)>,
+    kinematics: Query<Entity, With<KinematicBody>>,
+    sensors: Query<(), With<Sensor>>,
+    layers: Query<&CollisionLayers>,
+    index: Res<SpatialIndex>,
 
     mut ev_broad: EventWriter<BroadEvent>,
 ) {
-    for (a_ent, a_body, a_trans) in kinematics.iter() {
-        let kin = Vec::<Entity>::new();
-        let mut sta = Vec::<Entity>::new();
-        let a_box = Aabb::from_rectangle(a_body.shape, a_trans).get_broad(a_body.motion);
-
-        for (b_ent, b_body, b_trans) in statics.iter() {
-            let b_box = Aabb::from_rectangle(b_body.shape, b_trans);
+    let entities: Vec<Entity> = kinematics.iter().collect();
+    let mut candidates: HashMap<Entity, (Vec<Entity>, Vec<Entity>)> = entities.iter()
+        .map(|&entity| (entity, (Vec::new(), Vec::new())))
+        .collect();
+
+    for (a_ent, b_ent) in index.query_pairs(&entities) {
+        if !interacts(&layers, a_ent, b_ent) { continue; }
+        if sensors.get(a_ent).is_ok() || sensors.get(b_ent).is_ok() { continue; }
+
+        let (kin, sta) = match candidates.get_mut(&a_ent) {
+            Some(candidates) => candidates,
+            None => continue,
+        };
 
-            if a_box.is_overlapping(&b_box) {
-                sta.push(b_ent);
-            }
+        match kinematics.get(b_ent) {
+            Ok(_) => kin.push(b_ent),
+            Err(_) => sta.push(b_ent),
         }
+    }
+
+    for a_ent in entities {
+        let (kin, sta) = candidates.remove(&a_ent).unwrap_or_default();
 
         ev_broad.send(BroadEvent {
             entity: a_ent,
@@ -63,6 +169,14 @@ pub fn broadphase(
     }
 }
 
+fn interacts(layers: &Query<&CollisionLayers>, a: Entity, b: Entity) -> bool {
+    layers_of(layers, a).interacts_with(&layers_of(layers, b))
+}
+
+fn layers_of(layers: &Query<&CollisionLayers>, entity: Entity) -> CollisionLayers {
+    layers.get(entity).copied().unwrap_or_default()
+}
+
 pub fn narrowphase(
     kinematics: Query<(&KinematicBody, &GlobalTransform)>,
     statics: Query<(&StaticBody, &GlobalTransform)>,
@@ -75,38 +189,54 @@ pub fn narrowphase(
             Ok((body, trans)) => (body, trans),
             Err(_) => continue,
         };
-        let a_box = Aabb::from_rectangle(a_body.shape, a_trans);
+        let a_col = Collider::from_shape(&a_body.shape, a_trans, a_body.rotation_mode);
 
         let mut sta_col: Vec<Collisions> = Vec::new();
         for &b_ent in ev.statics.iter() {
-            let (b_body, b_trans) = match statics.get(b_ent) {
+            let (b_col, b_motion) = match statics.get(b_ent) {
+                Ok((body, trans)) => (Collider::from_shape(&body.shape, trans, body.rotation_mode), body.motion),
+                Err(_) => continue,
+            };
+
+            match a_col.sweep_test(&b_col, a_body.total_motion() - b_motion) {
+                Some(hit) => push_collision(&mut sta_col, hit.time, b_ent),
+                None => continue,
+            };
+        }
+
+        let mut kin_col: Vec<Collisions> = Vec::new();
+        for &b_ent in ev.kinematics.iter() {
+            let (b_body, b_trans) = match kinematics.get(b_ent) {
                 Ok((body, trans)) => (body, trans),
                 Err(_) => continue,
             };
-            let b_box = Aabb::from_rectangle(b_body.shape, b_trans);
-
-            match a_box.sweep_test(&b_box, a_body.motion) {
-                Some(hit) => {
-                    match sta_col.iter_mut().find(|col| col.time == hit.time) {
-                        Some(col) => col.entities.push(b_ent),
-                        None => sta_col.push(Collisions { time: hit.time, entities: vec![b_ent] }),
-                    }
-                    
-                },
+            let b_col = Collider::from_shape(&b_body.shape, b_trans, b_body.rotation_mode);
+
+            // both bodies are moving, so sweep with the motion relative to the other one
+            match a_col.sweep_test(&b_col, a_body.total_motion() - b_body.total_motion()) {
+                Some(hit) => push_collision(&mut kin_col, hit.time, b_ent),
                 None => continue,
             };
         }
 
         sta_col.sort_by(|a, b| (a.time).partial_cmp(&b.time).unwrap());
+        kin_col.sort_by(|a, b| (a.time).partial_cmp(&b.time).unwrap());
 
         ev_narrow.send(NarrowEvent {
             entity: ev.entity,
-            kinematics: Vec::new(),
+            kinematics: kin_col,
             statics: sta_col,
         })
     }
 }
 
+fn push_collision(collisions: &mut Vec<Collisions>, time: f32, entity: Entity) {
+    match collisions.iter_mut().find(|col| col.time == time) {
+        Some(col) => col.entities.push(entity),
+        None => collisions.push(Collisions { time, entities: vec![entity] }),
+    }
+}
+
 pub fn clear_contacts(
     mut kinematics: Query<&mut KinematicBody>,
 ) {
@@ -116,76 +246,291 @@ pub fn clear_contacts(
 }
 
 pub fn solve(
-    mut kinematics: Query<(&mut KinematicBody, &GlobalTransform)>,
-    statics: Query<(&StaticBody, &GlobalTransform)>,
+    mut kinematics: Query<(&mut KinematicBody, &GlobalTransform, Option<&Sensor>)>,
+    statics: Query<(&StaticBody, &GlobalTransform), Without<Sensor>>,
+    layers: Query<&CollisionLayers>,
+    index: Res<SpatialIndex>,
+    settings: Res<SolverSettings>,
 
     mut ev_narrow: EventReader<NarrowEvent>,
     mut ev_move: EventWriter<MoveEvent>,
 ) {
-    for ev in ev_narrow.iter() {
-        let (mut a_body, a_trans) = match kinematics.get_mut(ev.entity) {
-            Ok((body, trans)) => (body, trans),
+    let events: Vec<&NarrowEvent> = ev_narrow.iter().collect();
+
+    // statics are solved first for every body, so kinematic pairs
+    // are solved against the motion the other body will actually do
+    let mut solved: HashMap<Entity, (Collider, Vec2, Vec<Contact>)> = HashMap::default();
+    let mut dropped: HashSet<Entity> = HashSet::default();
+
+    for ev in events.iter() {
+        let (a_body, a_trans, a_sensor) = match kinematics.get(ev.entity) {
+            Ok((body, trans, sensor)) => (body, trans, sensor),
             Err(_) => continue,
         };
-        let a_box = Aabb::from_rectangle(a_body.shape, a_trans);
-        let mut a_motion = a_body.motion;
+        let a_col = Collider::from_shape(&a_body.shape, a_trans, a_body.rotation_mode);
+        let a_box = a_col.aabb();
+
+        // sensors go through everything
+        if a_sensor.is_some() {
+            solved.insert(ev.entity, (a_col, a_body.total_motion(), Vec::new()));
+            continue;
+        }
+
+        let static_collider = |b_ent: Entity| {
+            let (b_body, b_trans) = statics.get(b_ent).ok()?;
+            Some((b_ent, b_body, Collider::from_shape(&b_body.shape, b_trans, b_body.rotation_mode)))
+        };
+
+        // the first sweep only needs the statics the narrowphase found,
+        // the world is queried again for the next ones
+        let seed = ev.statics.iter()
+            .flat_map(|col| col.entities.iter())
+            .filter_map(|&b_ent| static_collider(b_ent))
+            .collect();
+        let candidates = |region: &Aabb| index.query_aabb(region).into_iter()
+            .filter(|&b_ent| interacts(&layers, ev.entity, b_ent))
+            .filter_map(static_collider)
+            .collect();
+        let (a_motion, contacts) = slide_statics(a_body, &a_col, a_body.total_motion(), settings.max_slides, Some(seed), candidates);
+
+        if a_body.drop_through {
+            let skin = Aabb::new(a_box.extents() + Vec2::splat(ONE_WAY_SKIN), a_box.position());
+            let is_clear = index.query_aabb(&skin.get_broad(a_motion)).into_iter()
+                .all(|b_ent| statics.get(b_ent).map_or(true, |(b_body, _)| b_body.one_way.is_none()));
+
+            if is_clear { dropped.insert(ev.entity); }
+        }
+
+        solved.insert(ev.entity, (a_col, a_motion, contacts));
+    }
+
+    for ev in events.iter() {
+        let (a_col, mut a_motion, mut contacts) = match solved.get(&ev.entity) {
+            Some((a_col, a_motion, contacts)) => (a_col, *a_motion, contacts.clone()),
+            None => continue,
+        };
 
-        for col in ev.statics.iter() {
+        for col in ev.kinematics.iter() {
             for &b_ent in col.entities.iter() {
-                let (b_body, b_trans) = match statics.get(b_ent) {
-                    Ok((body, trans)) => (body, trans),
-                    Err(_) => continue,
-                };
-                let b_box = Aabb::from_rectangle(b_body.shape, b_trans);
-                
-                if !a_box.get_broad(a_motion).is_overlapping(&b_box) { continue; }
-
-                let has_collided;
-                let mut contact_normal;
-                
-                let mut is_diagonal = false;
-                match a_box.sweep_test(&b_box, a_motion) {
-                    Some(hit) => {
-                        slide_motion(&mut a_motion, hit.normal, hit.time);
-                        
-                        if hit.normal == Vec2::ZERO && col.entities.len() <= 1 {
-                            is_diagonal = true;
-                        }
-                        
-                        has_collided = true;
-                        contact_normal = hit.normal;
-                    },
+                let (b_col, b_motion) = match solved.get(&b_ent) {
+                    Some((b_col, b_motion, _)) => (b_col, *b_motion),
                     None => continue,
-                }
-
-                if is_diagonal {
-                    let ghost_box = Aabb::new(
-                        b_box.extents() + a_box.extents() * DIAGONAL_SOLVE,
-                        b_box.position(),
-                    );
-
-                    match a_box.sweep_test(&ghost_box, a_motion) {
-                        Some(hit) => {
-                            slide_motion(&mut a_motion, hit.normal, hit.time);
-                            contact_normal = hit.normal;
-                        },
-                        None => continue,
-                    }
-                }
+                };
 
-                if has_collided && contact_normal != Vec2::ZERO {
-                    a_body.contacts.push(Contact {
-                        entity: b_ent,
-                        normal: contact_normal,
-                    })
+                if let Some(contact) = solve_kinematic(a_col, &mut a_motion, b_ent, b_col, b_motion) {
+                    contacts.push(contact);
                 }
             }
         }
 
+        let (mut a_body, a_trans) = match kinematics.get_mut(ev.entity) {
+            Ok((body, trans, _)) => (body, trans),
+            Err(_) => continue,
+        };
+        a_body.contacts.extend(contacts);
+        if dropped.contains(&ev.entity) { a_body.drop_through = false; }
+
         ev_move.send(MoveEvent {
             entity: ev.entity,
-            position: a_box.position() + a_motion,
+            position: Vec2::new(a_trans.translation.x, a_trans.translation.y) + a_motion,
+        })
+    }
+}
+
+/// Moves `a_col` by `motion` through the statics returned by `candidates`
+/// for a given region, giving the motion it actually does and its contacts.
+///
+/// Each step sweeps to the earliest hit, slides what is left of the motion
+/// along its normal, and queries the statics again from there, until nothing
+/// is hit or `max_slides` steps are done. The first step sweeps `seed` instead
+/// of querying when given.
+fn slide_statics<'a>(
+    a_body: &KinematicBody,
+    a_col: &Collider,
+    motion: Vec2,
+    max_slides: usize,
+    mut seed: Option<Vec<(Entity, &'a StaticBody, Collider)>>,
+    candidates: impl Fn(&Aabb) -> Vec<(Entity, &'a StaticBody, Collider)>,
+) -> (Vec2, Vec<Contact>) {
+    let mut done = Vec2::ZERO;
+    let mut remaining = motion;
+    // part of the frame already gone, as moving statics keep moving during it
+    let mut elapsed = 0.0;
+    let mut contacts: Vec<Contact> = Vec::new();
+
+    for _ in 0..max_slides {
+        let a_moved = a_col.translated(done);
+        let rest = 1.0 - elapsed;
+
+        let mut hits: Vec<_> = seed.take().unwrap_or_else(|| candidates(&a_moved.aabb().get_broad(remaining))).into_iter()
+            .filter_map(|(b_ent, b_body, b_col)| {
+                let b_col = b_col.translated(b_body.motion * elapsed);
+                let relative = remaining - b_body.motion * rest;
+
+                // `move_platforms` already pushes the body out of moving statics it overlaps
+                if b_body.motion != Vec2::ZERO && a_moved.is_overlapping(&b_col) { return None; }
+
+                let (hit, own_shape, shape) = a_moved.sweep_parts(&b_col, relative)?;
+                if passes_one_way(a_body, &a_moved, b_body, &b_col, hit.normal) { return None; }
+
+                Some((hit, relative, b_body.motion, b_col, Contact { entity: b_ent, normal: hit.normal, shape, own_shape }))
+            })
+            .collect();
+        // on ties, a proper normal beats a corner
+        hits.sort_by(|(a, ..), (b, ..)| a.time.partial_cmp(&b.time).unwrap()
+            .then((a.normal == Vec2::ZERO).cmp(&(b.normal == Vec2::ZERO))));
+
+        // internal edges are only looked for from the earliest hit,
+        // moving on to the next one when nothing is left to hit
+        let first = hits.into_iter().find_map(|(hit, relative, b_motion, b_col, mut contact)| {
+            if let Collider::Aabb(b_box) = b_col {
+                if b_motion == Vec2::ZERO {
+                    let a_box = a_moved.aabb();
+                    let a_box = Aabb::new(a_box.extents(), a_box.position() + relative * hit.time);
+                    contact.normal = external_normal(contact.entity, &b_box, hit.normal, &a_box, &candidates)?;
+                }
+            }
+
+            Some((hit, relative, b_motion, contact))
+        });
+
+        let (hit, relative, b_motion, mut contact) = match first {
+            Some(first) => first,
+            None => {
+                done += remaining;
+                break;
+            },
+        };
+
+        // a lone corner hit head on is landed on or hung from, never slid off sideways
+        if contact.normal == Vec2::ZERO {
+            contact.normal = match relative.y {
+                y if y != 0.0 => Vec2::new(0.0, -y.signum()),
+                _ => Vec2::new(-relative.x.signum(), 0.0),
+            };
+        }
+        let normal = contact.normal;
+
+        done += remaining * hit.time + normal * SLIDE_SKIN;
+        elapsed += rest * hit.time;
+        remaining *= 1.0 - hit.time;
+
+        // moving statics push the body along the normal, which otherwise
+        // keeps only the part of its motion that doesn't go into the surface
+        let along = remaining.dot(normal);
+        let pushed = b_motion.dot(normal) * (1.0 - elapsed);
+        remaining += normal * (along.max(pushed) - along);
+
+        if !contacts.iter().any(|other| other.entity == contact.entity) {
+            contacts.push(contact);
+        }
+    }
+
+    (done, contacts)
+}
+
+/// `normal` of a hit against the still rectangle `b_box` without the parts
+/// pointing out of faces shared with neighbouring rectangles, which are inside
+/// the floor or wall they make up. `None` when nothing is left to hit.
+fn external_normal<'a>(
+    b_ent: Entity,
+    b_box: &Aabb,
+    normal: Vec2,
+    a_box: &Aabb,
+    candidates: &impl Fn(&Aabb) -> Vec<(Entity, &'a StaticBody, Collider)>,
+) -> Option<Vec2> {
+    let face = |side: f32, axis: Vec2| match side.abs() > INTERNAL_EDGE_TOLERANCE {
+        true => axis * side.signum(),
+        false => Vec2::ZERO,
+    };
+    let (face_x, face_y) = (face(normal.x, Vec2::X), face(normal.y, Vec2::Y));
+    let is_internal = |face: Vec2| face != Vec2::ZERO && is_internal_face(b_ent, b_box, face, a_box, candidates);
+
+    // a corner next to an internal face is just part of the other face
+    match (is_internal(face_x), is_internal(face_y)) {
+        (false, false) => Some(normal),
+        (true, false) if face_y != Vec2::ZERO => Some(face_y),
+        (false, true) if face_x != Vec2::ZERO => Some(face_x),
+        _ => None,
+    }
+}
+
+/// Whether the side of `b_box` facing `face` is covered by other still
+/// rectangles where `a_box` touches it.
+fn is_internal_face<'a>(
+    b_ent: Entity,
+    b_box: &Aabb,
+    face: Vec2,
+    a_box: &Aabb,
+    candidates: &impl Fn(&Aabb) -> Vec<(Entity, &'a StaticBody, Collider)>,
+) -> bool {
+    let (axis, perp) = if face.x != 0.0 { (0, 1) } else { (1, 0) };
+    let edge = if face[axis] > 0.0 { b_box.max()[axis] } else { b_box.min()[axis] };
+
+    let mut extents = b_box.extents();
+    let mut position = b_box.position();
+    extents[axis] = INTERNAL_EDGE_TOLERANCE;
+    position[axis] = edge;
+
+    let mut spans: Vec<(f32, f32)> = candidates(&Aabb::new(extents, position)).into_iter()
+        .filter(|(c_ent, c_body, _)| *c_ent != b_ent && c_body.motion == Vec2::ZERO && c_body.one_way.is_none())
+        .filter_map(|(_, _, c_col)| match c_col {
+            Collider::Aabb(c_box) => Some(c_box),
+            _ => None,
         })
+        .filter(|c_box| {
+            let c_edge = if face[axis] > 0.0 { c_box.min()[axis] } else { c_box.max()[axis] };
+            (c_edge - edge).abs() < INTERNAL_EDGE_TOLERANCE
+        })
+        .map(|c_box| (c_box.min()[perp], c_box.max()[perp]))
+        .collect();
+    spans.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+
+    let start = a_box.min()[perp].max(b_box.min()[perp]);
+    let end = a_box.max()[perp].min(b_box.max()[perp]).max(start);
+
+    let mut covered = start;
+    for (min, max) in spans {
+        if min > covered + INTERNAL_EDGE_TOLERANCE { break; }
+        covered = covered.max(max);
+    }
+
+    covered >= end - INTERNAL_EDGE_TOLERANCE
+}
+
+/// Whether a one-way `b_body` lets `a_body` through when hit with `normal`.
+fn passes_one_way(a_body: &KinematicBody, a_col: &Collider, b_body: &StaticBody, b_col: &Collider, normal: Vec2) -> bool {
+    match b_body.one_way {
+        Some(direction) => a_body.drop_through
+            || a_col.is_overlapping(b_col)
+            || normal.dot(direction) <= 0.0,
+        None => false,
+    }
+}
+
+/// Resolves `a_col` moving by `a_motion` against a kinematic body moving by `b_motion`.
+fn solve_kinematic(a_col: &Collider, a_motion: &mut Vec2, b_ent: Entity, b_col: &Collider, b_motion: Vec2) -> Option<Contact> {
+    let relative = *a_motion - b_motion;
+
+    if !a_col.aabb().get_broad(relative).is_overlapping(&b_col.aabb()) { return None; }
+
+    // both bodies stop along the normal at the same time of impact, unless
+    // the other one moves away, so they end up touching and keep sliding
+    // along each other
+    match a_col.sweep_parts(b_col, relative)? {
+        (hit, _, _) if hit.normal == Vec2::ZERO => {
+            *a_motion *= hit.time;
+            None
+        },
+        (hit, own_shape, shape) => {
+            stop_motion(a_motion, b_motion, hit.normal, hit.time);
+            Some(Contact {
+                entity: b_ent,
+                normal: hit.normal,
+                shape,
+                own_shape,
+            })
+        },
     }
 }
 
@@ -207,25 +552,471 @@ pub fn move_entities(
     }
 }
 
+/// Moves the statics with a `motion` before kinematics are solved, pushing
+/// the kinematics they sweep or overlap out of where they end up.
+pub fn move_platforms(
+    mut statics: Query<Platform>,
+    mut kinematics: Query<(Entity, &mut KinematicBody, &GlobalTransform), Without<Sensor>>,
+    globals: Query<&GlobalTransform>,
+    layers: Query<&CollisionLayers>,
+) {
+    for (b_ent, b_body, mut b_transform, b_trans, b_parent, b_sensor) in statics.iter_mut() {
+        if b_body.motion == Vec2::ZERO { continue; }
+
+        // `motion` is in world space, the translation in the space of the parent
+        let motion = match b_parent.and_then(|parent| globals.get(parent.0).ok()) {
+            Some(parent) => parent.compute_matrix().inverse().transform_vector3(b_body.motion.extend(0.0)),
+            None => b_body.motion.extend(0.0),
+        };
+        b_transform.translation.x += motion.x;
+        b_transform.translation.y += motion.y;
+
+        if b_sensor.is_some() { continue; }
+        let b_col = Collider::from_shape(&b_body.shape, b_trans, b_body.rotation_mode);
+
+        for (a_ent, mut a_body, a_trans) in kinematics.iter_mut() {
+            if !interacts(&layers, a_ent, b_ent) { continue; }
+            let a_col = Collider::from_shape(&a_body.shape, a_trans, a_body.rotation_mode);
+
+            if let Some(push) = platform_push(&a_col, a_body.carried, &b_col, b_body.motion) {
+                a_body.carried += push;
+            }
+        }
+    }
+}
+
+/// How far `b_col` moving by `b_motion` pushes `a_col`, already carried by
+/// `carried`, so they end up touching. Bodies overlapping from the start
+/// get out along the axis needing the shortest push.
+fn platform_push(a_col: &Collider, carried: Vec2, b_col: &Collider, b_motion: Vec2) -> Option<Vec2> {
+    let a_end = a_col.translated(carried);
+    let b_end = b_col.translated(b_motion);
+
+    let normal = match a_col.is_overlapping(b_col) {
+        true => Vec2::ZERO,
+        false => b_col.sweep_test(a_col, b_motion - carried)?.normal,
+    };
+
+    // the normal is the one of the face of `a_col` that got hit
+    if normal != Vec2::ZERO { return push_out(&a_end, &b_end, -normal); }
+
+    [Vec2::X, -Vec2::X, Vec2::Y, -Vec2::Y].into_iter()
+        .filter_map(|direction| push_out(&a_end, &b_end, direction))
+        .min_by(|a, b| a.length().partial_cmp(&b.length()).unwrap())
+}
+
+/// Motion along `direction` taking `a_col` out of `b_col`, `None` if they don't overlap.
+fn push_out(a_col: &Collider, b_col: &Collider, direction: Vec2) -> Option<Vec2> {
+    let (a_box, b_box) = (a_col.aabb(), b_col.aabb());
+    let distance = (a_box.extents() + b_box.extents()).length() * 2.0 + a_box.position().distance(b_box.position());
+
+    // coming back from far enough along `direction` stops where they start touching
+    let hit = a_col.translated(direction * distance).sweep_test(b_col, -direction * distance)?;
+    Some(direction * (distance * (1.0 - hit.time) + PUSH_SKIN))
+}
+
 pub fn raycasts(
-    mut rays: Query<(&mut Raycast, &GlobalTransform)>,
-    statics: Query<(Entity, &StaticBody, &GlobalTransform)>,
+    mut rays: Query<(Entity, &mut Raycast, &GlobalTransform)>,
+    parents: Query<&Parent>,
+    layers: Query<&CollisionLayers>,
+    spatial_query: SpatialQuery,
 ) {
-    for (mut a_ray, a_trans) in rays.iter_mut() {
+    for (a_ent, mut a_ray, a_trans) in rays.iter_mut() {
         let raycast = Ray::from_ray(&a_ray, a_trans);
-        a_ray.hits.clear();
-        
-        let a_box = Aabb::from_ray(&a_ray, a_trans);
-        for (b_ent, b_body, b_trans) in statics.iter() {
-            let b_box = Aabb::from_rectangle(b_body.shape, b_trans);
-            
-            if a_box.is_overlapping(&b_box) {
-                match raycast.intersect_aabb(b_box) {
-                    Some(hit) => a_ray.hits.push((b_ent, hit)),
-                    None => continue,
-                }
+        let filter = cast_filter(a_ent, &layers, &parents, a_ray.hit_ancestors, &a_ray.include, &a_ray.exclude);
+
+        a_ray.hits = if a_ray.stop_at_first {
+            spatial_query.cast_ray(raycast.position, raycast.direction, &filter).into_iter().collect()
+        } else {
+            spatial_query.cast_ray_all(raycast.position, raycast.direction, &filter)
+        };
+    }
+}
+
+pub fn shape_casts(
+    mut casts: Query<(Entity, &mut ShapeCast, &GlobalTransform)>,
+    parents: Query<&Parent>,
+    layers: Query<&CollisionLayers>,
+    spatial_query: SpatialQuery,
+) {
+    for (a_ent, mut a_cast, a_trans) in casts.iter_mut() {
+        let position = Vec2::new(a_trans.translation.x, a_trans.translation.y) + a_cast.offset;
+        let filter = cast_filter(a_ent, &layers, &parents, a_cast.hit_ancestors, &a_cast.include, &a_cast.exclude);
+
+        a_cast.hits = if a_cast.stop_at_first {
+            spatial_query.cast_shape(&a_cast.shape, position, a_cast.direction, &filter).into_iter().collect()
+        } else {
+            spatial_query.cast_shape_all(&a_cast.shape, position, a_cast.direction, &filter)
+        };
+    }
+}
+
+/// Filter of a `Raycast` or `ShapeCast` attached to `entity`.
+fn cast_filter(
+    entity: Entity,
+    layers: &Query<&CollisionLayers>,
+    parents: &Query<&Parent>,
+    hit_ancestors: bool,
+    include: &[Entity],
+    exclude: &[Entity],
+) -> QueryFilter {
+    let mut filter = QueryFilter::new()
+        .with_layers(layers_of(layers, entity));
+    filter.included.extend(include.iter().copied());
+    filter.excluded.extend(exclude.iter().copied());
+
+    if !hit_ancestors {
+        let mut ancestor = Some(entity);
+        while let Some(entity) = ancestor {
+            filter.excluded.push(entity);
+            ancestor = parents.get(entity).ok().map(|parent| parent.0);
+        }
+    }
+
+    filter
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use crate::collision::{ slide_statics, Aabb, Collider };
+    use crate::components::{ CollisionLayers, KinematicBody, PhysicsLayer, Raycast, RaycastBundle, Rectangle, Sensor, Slope, StaticBody };
+    use crate::plugin::PhysicsPlugin;
+
+    fn tile() -> StaticBody {
+        StaticBody::new(Rectangle::new().with_size(Vec2::splat(2.0)))
+    }
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_plugin(TransformPlugin).add_plugin(PhysicsPlugin);
+        app
+    }
+
+    fn spawn_kinematic(app: &mut App, position: Vec2, motion: Vec2) -> Entity {
+        let mut body = KinematicBody::new(Rectangle::new().with_size(Vec2::splat(2.0)));
+        body.motion = motion;
+
+        let transform = Transform::from_translation(position.extend(0.0));
+        app.world.spawn().insert(body).insert(transform).insert(GlobalTransform::from(transform)).id()
+    }
+
+    fn spawn_static(app: &mut App, body: StaticBody, position: Vec2) -> Entity {
+        let transform = Transform::from_translation(position.extend(0.0));
+        app.world.spawn().insert(body).insert(transform).insert(GlobalTransform::from(transform)).id()
+    }
+
+    fn position(app: &App, entity: Entity) -> Vec2 {
+        app.world.get::<Transform>(entity).unwrap().translation.truncate()
+    }
+
+    #[test]
+    fn test_kinematic_pairs() {
+        let mut app = app();
+        // one body chasing a slower one, and two bodies moving head on
+        let chaser = spawn_kinematic(&mut app, Vec2::ZERO, Vec2::new(4.0, 0.0));
+        let chased = spawn_kinematic(&mut app, Vec2::new(3.0, 0.0), Vec2::new(2.0, 0.0));
+        let left = spawn_kinematic(&mut app, Vec2::new(0.0, 10.0), Vec2::new(2.0, 0.0));
+        let right = spawn_kinematic(&mut app, Vec2::new(4.0, 10.0), Vec2::new(-2.0, 0.0));
+
+        app.update();
+
+        assert!((position(&app, chased) - Vec2::new(5.0, 0.0)).length() < 0.001);
+        assert!((position(&app, chaser) - Vec2::new(3.0, 0.0)).length() < 0.001);
+        assert!((position(&app, left) - Vec2::new(1.0, 10.0)).length() < 0.001);
+        assert!((position(&app, right) - Vec2::new(3.0, 10.0)).length() < 0.001);
+
+        for entity in [chaser, left, right] {
+            assert_eq!(app.world.get::<KinematicBody>(entity).unwrap().contacts.len(), 1);
+        }
+    }
+
+    #[derive(Clone, Copy)]
+    enum Layer {
+        Ground,
+        Enemy,
+    }
+
+    impl PhysicsLayer for Layer {
+        fn to_bits(&self) -> u32 {
+            1 << (*self as u32)
+        }
+    }
+
+    #[test]
+    fn test_slide_corner() {
+        // a floor tile and a wall tile on top of it to the right
+        let (floor, wall) = (tile(), tile());
+        let statics = [
+            (Entity::from_raw(0), &floor, Collider::Aabb(Aabb::new(Vec2::splat(1.0), Vec2::new(0.0, -2.0)))),
+            (Entity::from_raw(1), &wall, Collider::Aabb(Aabb::new(Vec2::splat(1.0), Vec2::new(3.0, 0.0)))),
+        ];
+        let candidates = |region: &Aabb| statics.iter()
+            .filter(|(_, _, b_col)| b_col.aabb().is_overlapping(region))
+            .map(|(b_ent, b_body, b_col)| (*b_ent, *b_body, b_col.clone()))
+            .collect();
+
+        let body = KinematicBody::new(Rectangle::new().with_size(Vec2::splat(2.0)));
+        let a_col = Collider::Aabb(Aabb::new(Vec2::splat(1.0), Vec2::new(-2.0, 1.0)));
+
+        let (motion, contacts) = slide_statics(&body, &a_col, Vec2::new(4.0, -2.0), 4, None, candidates);
+        assert!((motion - Vec2::new(3.0, -1.0)).length() < 0.01);
+        assert_eq!(contacts.len(), 2);
+    }
+
+    #[test]
+    fn test_layers() {
+        let mut app = app();
+        let ground = CollisionLayers::none().with_membership(Layer::Ground).with_filter(Layer::Enemy);
+        let enemy = CollisionLayers::none().with_membership(Layer::Enemy).with_filter(Layer::Ground);
+
+        // an enemy walking through another one up to a wall
+        let wall = spawn_static(&mut app, tile(), Vec2::new(6.0, 0.0));
+        let walker = spawn_kinematic(&mut app, Vec2::ZERO, Vec2::new(3.0, 0.0));
+        let other = spawn_kinematic(&mut app, Vec2::new(3.0, 0.0), Vec2::ZERO);
+        app.world.entity_mut(wall).insert(ground);
+        app.world.entity_mut(walker).insert(enemy);
+        app.world.entity_mut(other).insert(enemy);
+
+        // a ray only looking for the ground, going through an enemy crate
+        let ground_wall = spawn_static(&mut app, tile(), Vec2::new(6.0, 10.0));
+        let enemy_crate = spawn_static(&mut app, tile(), Vec2::new(3.0, 10.0));
+        app.world.entity_mut(ground_wall).insert(ground);
+        app.world.entity_mut(enemy_crate).insert(enemy);
+        let ray = app.world.spawn()
+            .insert_bundle(RaycastBundle {
+                raycast: Raycast::new().with_direction(Vec2::new(10.0, 0.0)),
+                transform_bundle: TransformBundle::from(Transform::from_xyz(0.0, 10.0, 0.0)),
+            })
+            .insert(CollisionLayers::none().with_filter(Layer::Ground))
+            .id();
+
+        app.update();
+        assert!((position(&app, walker) - Vec2::new(3.0, 0.0)).length() < 0.001);
+        assert!(app.world.get::<KinematicBody>(walker).unwrap().contacts.is_empty());
+        assert_eq!(app.world.get::<Raycast>(ray).unwrap().get_hits().iter().map(|hit| hit.entity).collect::<Vec<_>>(), vec![ground_wall]);
+
+        app.update();
+        assert!((position(&app, walker) - Vec2::new(4.0, 0.0)).length() < 0.01);
+        assert_eq!(app.world.get::<KinematicBody>(walker).unwrap().contacts[0].entity(), wall);
+    }
+
+    #[test]
+    fn test_raycasts() {
+        let mut app = app();
+        // a shooter at the start of its rays, with a body and a wall in front of it
+        let shooter = spawn_kinematic(&mut app, Vec2::ZERO, Vec2::ZERO);
+        let body = spawn_kinematic(&mut app, Vec2::new(4.0, 0.0), Vec2::ZERO);
+        let wall = spawn_static(&mut app, tile(), Vec2::new(8.0, 0.0));
+
+        let raycast = || Raycast::new().with_direction(Vec2::new(10.0, 0.0));
+        let rays = [
+            raycast(),
+            raycast().with_hit_ancestors(true),
+            raycast().with_included(wall),
+            raycast().with_excluded(body),
+            raycast().with_stop_at_first(true),
+        ].map(|raycast| app.world.spawn().insert_bundle(RaycastBundle { raycast, ..default() }).id());
+        app.world.entity_mut(shooter).push_children(&rays);
+
+        app.update();
+        let hits = |ray: Entity| app.world.get::<Raycast>(ray).unwrap().get_hits().iter().map(|hit| hit.entity).collect::<Vec<_>>();
+        assert_eq!(hits(rays[0]), vec![body, wall]);
+        assert_eq!(hits(rays[1]), vec![shooter, body, wall]);
+        assert_eq!(hits(rays[2]), vec![wall]);
+        assert_eq!(hits(rays[3]), vec![wall]);
+        assert_eq!(hits(rays[4]), vec![body]);
+
+        let raycast = app.world.get::<Raycast>(rays[0]).unwrap();
+        assert_eq!(raycast.first_hit().unwrap().entity, body);
+        assert_eq!(raycast.get_hits().iter().map(|hit| hit.time).collect::<Vec<_>>(), vec![0.3, 0.7]);
+    }
+
+    #[test]
+    fn test_sensor_kinematic() {
+        let mut app = app();
+        spawn_static(&mut app, tile(), Vec2::new(3.0, 0.0));
+        let sensor = spawn_kinematic(&mut app, Vec2::ZERO, Vec2::new(4.0, 0.0));
+        app.world.entity_mut(sensor).insert(Sensor);
+
+        app.update();
+        assert_eq!(position(&app, sensor), Vec2::new(4.0, 0.0));
+        assert!(app.world.get::<KinematicBody>(sensor).unwrap().contacts.is_empty());
+    }
+
+    #[test]
+    fn test_one_way() {
+        let mut app = app();
+        for x in [0.0, 10.0, 20.0] {
+            spawn_static(&mut app, tile().with_one_way(Vec2::Y), Vec2::new(x, 0.0));
+        }
+
+        // jumping through from below, landing from above, and dropping through
+        let below = spawn_kinematic(&mut app, Vec2::new(0.0, -3.0), Vec2::new(0.0, 4.0));
+        let above = spawn_kinematic(&mut app, Vec2::new(10.0, 3.0), Vec2::new(0.0, -4.0));
+        let dropping = spawn_kinematic(&mut app, Vec2::new(20.0, 3.0), Vec2::new(0.0, -4.0));
+        app.world.get_mut::<KinematicBody>(dropping).unwrap().drop_through = true;
+
+        app.update();
+        assert_eq!(position(&app, below), Vec2::new(0.0, 1.0));
+        assert!(app.world.get::<KinematicBody>(below).unwrap().contacts.is_empty());
+        assert!((position(&app, above) - Vec2::new(10.0, 2.0)).length() < 0.01);
+        assert_eq!(app.world.get::<KinematicBody>(above).unwrap().contacts[0].normal(), Vec2::Y);
+        assert_eq!(position(&app, dropping), Vec2::new(20.0, -1.0));
+        assert!(app.world.get::<KinematicBody>(dropping).unwrap().contacts.is_empty());
+    }
+
+    #[test]
+    fn test_drop_through() {
+        let mut app = app();
+        spawn_static(&mut app, tile().with_one_way(Vec2::Y), Vec2::ZERO);
+        let body = spawn_kinematic(&mut app, Vec2::new(0.0, 2.5), Vec2::new(0.0, -1.0));
+        app.world.get_mut::<KinematicBody>(body).unwrap().drop_through = true;
+
+        // the flag stays on while the body goes through the platform
+        for _ in 0..5 {
+            app.update();
+            assert!(app.world.get::<KinematicBody>(body).unwrap().drop_through);
+        }
+
+        app.update();
+        assert!(!app.world.get::<KinematicBody>(body).unwrap().drop_through);
+        assert!((position(&app, body) - Vec2::new(0.0, -3.5)).length() < 0.001);
+    }
+
+    #[test]
+    fn test_ramps() {
+        let mut app = app();
+        // floors rising to the right along y = x around their centers
+        let ramp = || StaticBody::new(Slope::new().with_size(Vec2::splat(8.0)));
+        let up_ramp = spawn_static(&mut app, ramp(), Vec2::ZERO);
+        spawn_static(&mut app, ramp(), Vec2::new(20.0, 0.0));
+
+        // a body with its corner on the ramp walking up, and one walking down with gravity
+        let up = spawn_kinematic(&mut app, Vec2::new(-3.0, -0.99), Vec2::X);
+        let down = spawn_kinematic(&mut app, Vec2::new(21.0, 3.01), Vec2::new(-1.0, -2.0));
+        let ray = app.world.spawn()
+            .insert_bundle(RaycastBundle {
+                raycast: Raycast::new().with_direction(Vec2::new(0.0, -20.0)),
+                transform_bundle: TransformBundle::from(Transform::from_xyz(1.0, 10.0, 0.0)),
+            })
+            .id();
+
+        let normal = Vec2::new(-1.0, 1.0).normalize();
+        for _ in 0..3 {
+            let (up_start, down_start) = (position(&app, up), position(&app, down));
+            app.update();
+
+            // sliding along the ramp instead of stopping on it
+            assert!((position(&app, up) - up_start - Vec2::splat(0.5)).length() < 0.01);
+            assert!((position(&app, down) - down_start - Vec2::splat(-1.5)).length() < 0.01);
+            for entity in [up, down] {
+                let contacts = &app.world.get::<KinematicBody>(entity).unwrap().contacts;
+                assert!((contacts[0].normal() - normal).length() < 0.0001);
             }
         }
 
+        // the ray going down hits the ramp where y = x
+        let hit = app.world.get::<Raycast>(ray).unwrap().get_hits()[0];
+        assert_eq!(hit.entity, up_ramp);
+        assert!((hit.time - 0.45).abs() < 0.0001);
+        assert!((hit.normal - normal).length() < 0.0001);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_platforms() {
+        let mut app = app();
+        let moving = |motion: Vec2| StaticBody { motion, ..tile() };
+
+        // a body landing on a platform moving right, riding it from the next frame
+        let ride = spawn_static(&mut app, moving(Vec2::X), Vec2::ZERO);
+        let rider = spawn_kinematic(&mut app, Vec2::new(0.0, 2.001), Vec2::new(0.0, -0.5));
+        // a still body half a unit in front of a platform moving right
+        spawn_static(&mut app, moving(Vec2::X), Vec2::new(10.0, 0.0));
+        let pushed = spawn_kinematic(&mut app, Vec2::new(12.5, 0.0), Vec2::ZERO);
+        // a still body sunk half a unit in a platform moving up
+        spawn_static(&mut app, moving(Vec2::Y), Vec2::new(20.0, 0.0));
+        let sunk = spawn_kinematic(&mut app, Vec2::new(20.0, 1.5), Vec2::ZERO);
+        // a still body half a unit above a ramp moving up
+        let ramp = StaticBody { motion: Vec2::Y, ..StaticBody::new(Slope::new().with_size(Vec2::splat(2.0))) };
+        spawn_static(&mut app, ramp, Vec2::new(30.0, 0.0));
+        let lifted = spawn_kinematic(&mut app, Vec2::new(30.5, 2.5), Vec2::ZERO);
+        // a platform moving right under a parent scaled by 2, moving by its own motion in the world
+        let parent_transform = Transform::from_xyz(40.0, 0.0, 0.0).with_scale(Vec3::splat(2.0));
+        let parent = app.world.spawn().insert(parent_transform).insert(GlobalTransform::from(parent_transform)).id();
+        let child = app.world.spawn()
+            .insert(StaticBody { motion: Vec2::X, ..StaticBody::new(Rectangle::new().with_size(Vec2::splat(1.0))) })
+            .insert_bundle(TransformBundle::from(Transform::identity()))
+            .id();
+        app.world.entity_mut(parent).push_children(&[child]);
+
+        app.update();
+        assert!((position(&app, pushed) - Vec2::new(13.0, 0.0)).length() < 0.01);
+        assert!((position(&app, sunk) - Vec2::new(20.0, 3.0)).length() < 0.01);
+        assert!((position(&app, lifted) - Vec2::new(30.5, 3.0)).length() < 0.01);
+        assert_eq!(position(&app, child), Vec2::new(0.5, 0.0));
+
+        app.update();
+        app.update();
+        assert!((position(&app, ride) - Vec2::new(3.0, 0.0)).length() < 0.001);
+        assert!((position(&app, rider) - Vec2::new(2.0, 2.001)).length() < 0.01);
+        assert_eq!(app.world.get::<KinematicBody>(rider).unwrap().contacts[0].entity(), ride);
+        assert_eq!(app.world.get::<GlobalTransform>(child).unwrap().translation.truncate(), Vec2::new(43.0, 0.0));
+    }
+    /// Colliders of the tiles of the platformer example in a 1280 by 720 window.
+    fn platformer_tiles() -> Vec<(Entity, Collider)> {
+        const TILEMAP: [[u8; 16]; 9] = [
+            [1, 0, 1, 1, 1, 1, 0, 0, 1, 0, 1, 1, 1, 1, 1, 1],
+            [1, 0, 0, 1, 1, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1],
+            [1, 0, 1, 1, 1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1],
+            [1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 0, 1],
+            [1, 1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1],
+            [1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1],
+            [1, 1, 0, 0, 1, 1, 1, 1, 1, 0, 1, 1, 1, 1, 0, 1],
+            [1, 1, 1, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 1],
+            [1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1],
+        ];
+        let tile_size = Vec2::splat(80.0);
+
+        let mut tiles = Vec::new();
+        for (v, row) in TILEMAP.iter().enumerate() {
+            for (h, &tile) in row.iter().enumerate() {
+                if tile == 0 { continue; }
+
+                let position = Vec2::new(-640.0, 360.0) + Vec2::new(h as f32 + 0.5, -(v as f32) - 0.5) * tile_size;
+                tiles.push((Entity::from_raw(tiles.len() as u32), Collider::Aabb(Aabb::new(tile_size / 2.0, position))));
+            }
+        }
+
+        tiles
+    }
+
+    #[test]
+    fn test_tile_seams() {
+        let tiles = platformer_tiles();
+        let statics: Vec<StaticBody> = tiles.iter().map(|_| tile()).collect();
+        let candidates = |region: &Aabb| tiles.iter().zip(statics.iter())
+            .filter(|((_, b_col), _)| b_col.aabb().is_overlapping(region))
+            .map(|((b_ent, b_col), b_body)| (*b_ent, b_body, b_col.clone()))
+            .collect();
+
+        // the player of the example, slightly sunk in the floor of the sixth row
+        let body = KinematicBody::new(Rectangle::new().with_size(Vec2::new(48.0, 64.0)));
+        let mut a_col = Collider::Aabb(Aabb::new(Vec2::new(24.0, 32.0), Vec2::new(-280.0, -120.0 + 32.0 - 0.005)));
+
+        // walking right over the seams of four tiles
+        for _ in 0..50 {
+            let (motion, contacts) = slide_statics(&body, &a_col, Vec2::new(6.0, -0.5), 4, None, candidates);
+
+            assert!((motion.x - 6.0).abs() < 0.0001);
+            assert!(contacts.iter().all(|contact| contact.normal() == Vec2::Y));
+            a_col = a_col.translated(motion);
+        }
+
+        // running into the left wall still stops
+        let a_col = Collider::Aabb(Aabb::new(Vec2::new(24.0, 32.0), Vec2::new(-480.0, -120.0 + 32.0 + 0.5)));
+        let (motion, contacts) = slide_statics(&body, &a_col, Vec2::new(-80.0, 0.0), 4, None, candidates);
+        assert!(motion.x > -56.0);
+        assert_eq!(contacts[0].normal(), Vec2::X);
+    }
+}
@@ -0,0 +1,125 @@
+use bevy::prelude::Vec2;
+use crate::collision::{ Aabb, Hit, Polygon };
+
+/// Box whose local x axis points along `axis`.
+#[derive(Clone, Copy, Debug)]
+pub struct Obb {
+    extents: Vec2,
+    position: Vec2,
+    axis: Vec2,
+}
+
+impl Obb {
+    pub fn new(extents: Vec2, position: Vec2, axis: Vec2) -> Self {
+        let axis = axis.normalize_or_zero();
+
+        Self {
+            extents,
+            position,
+            axis: if axis == Vec2::ZERO { Vec2::X } else { axis },
+        }
+    }
+
+    pub fn from_aabb(aabb: &Aabb) -> Self {
+        Self::new(aabb.extents(), aabb.position(), Vec2::X)
+    }
+
+    pub fn extents(&self) -> Vec2 {
+        self.extents
+    }
+
+    pub fn position(&self) -> Vec2 {
+        self.position
+    }
+
+    /// Local x and y axes in world space.
+    pub fn axes(&self) -> [Vec2; 2] {
+        [self.axis, self.axis.perp()]
+    }
+
+    /// `point` in the space of the box, its center being the origin.
+    pub fn to_local(&self, point: Vec2) -> Vec2 {
+        let [x, y] = self.axes();
+        let delta = point - self.position;
+
+        Vec2::new(delta.dot(x), delta.dot(y))
+    }
+
+    /// Counter clockwise corners, starting from the local bottom left one.
+    pub fn vertices(&self) -> [Vec2; 4] {
+        let [x, y] = self.axes();
+        let (x, y) = (x * self.extents.x, y * self.extents.y);
+
+        [
+            self.position - x - y,
+            self.position + x - y,
+            self.position + x + y,
+            self.position - x + y,
+        ]
+    }
+
+    pub fn aabb(&self) -> Aabb {
+        let [x, y] = self.axes();
+
+        Aabb::new(x.abs() * self.extents.x + y.abs() * self.extents.y, self.position)
+    }
+
+    pub fn project(&self, axis: Vec2) -> (f32, f32) {
+        let [x, y] = self.axes();
+        let center = self.position.dot(axis);
+        let radius = self.extents.x * x.dot(axis).abs() + self.extents.y * y.dot(axis).abs();
+
+        (center - radius, center + radius)
+    }
+
+    pub fn is_overlapping(&self, other: &Obb) -> bool {
+        self.axes().into_iter().chain(other.axes()).all(|axis| {
+            let (a_min, a_max) = self.project(axis);
+            let (b_min, b_max) = other.project(axis);
+
+            a_min < b_max && a_max > b_min
+        })
+    }
+
+    pub fn sweep_aabb(&self, other: &Aabb, motion: Vec2) -> Option<Hit> {
+        Polygon::new(self.vertices().to_vec()).sweep_test(&Polygon::from_aabb(other), motion)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::collision::{ Aabb, Obb, Ray };
+    use bevy::math::Vec2;
+
+    #[test]
+    fn test_overlap() {
+        // a diamond whose corner points at a box it only overlaps as an Aabb
+        let a = Obb::new(Vec2::splat(1.0), Vec2::ZERO, Vec2::new(1.0, 1.0));
+        let b = Obb::from_aabb(&Aabb::new(Vec2::splat(0.5), Vec2::new(1.4, 1.4)));
+
+        assert!(a.aabb().is_overlapping(&b.aabb()));
+        assert!(!a.is_overlapping(&b));
+    }
+
+    #[test]
+    fn test_sweep_aabb() {
+        let a = Obb::new(Vec2::splat(1.0), Vec2::ZERO, Vec2::new(1.0, 1.0));
+        let b = Aabb::new(Vec2::splat(1.0), Vec2::new(0.0, -4.0));
+
+        let hit = a.sweep_aabb(&b, Vec2::new(0.0, -4.0)).unwrap();
+        assert!((hit.time - (3.0 - 2f32.sqrt()) / 4.0).abs() < 0.0001);
+        assert_eq!(hit.normal, Vec2::new(0.0, 1.0));
+    }
+
+    #[test]
+    fn test_ray() {
+        // standing up, so 2 wide and 4 tall
+        let a = Obb::new(Vec2::new(2.0, 1.0), Vec2::ZERO, Vec2::Y);
+        let ray = Ray::new(Vec2::new(4.0, 0.0), Vec2::new(-3.0, 1.5));
+
+        let hit = ray.intersect_obb(&a).unwrap();
+        assert!((hit.time - 0.5).abs() < 0.0001);
+        assert!((hit.normal - Vec2::new(-1.0, 0.0)).length() < 0.0001);
+        assert!(ray.intersect_obb(&Obb::new(Vec2::new(1.0, 2.0), Vec2::ZERO, Vec2::Y)).is_none());
+    }
+}
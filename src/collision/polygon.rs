@@ -0,0 +1,216 @@
+use bevy::prelude::Vec2;
+use crate::collision::{ Aabb, Hit };
+
+const TIE_TOLERANCE: f32 = 0.00001;
+
+/// Convex polygon in world space.
+#[derive(Clone, Debug)]
+pub struct Polygon {
+    vertices: Vec<Vec2>,
+}
+
+impl Polygon {
+    pub fn new(vertices: Vec<Vec2>) -> Self {
+        Self {
+            vertices,
+        }
+    }
+
+    /// Counter clockwise convex hull of `points`.
+    // https://en.wikibooks.org/wiki/Algorithm_Implementation/Geometry/Convex_hull/Monotone_chain
+    pub fn convex_hull(mut points: Vec<Vec2>) -> Self {
+        points.sort_by(|a, b| a.x.partial_cmp(&b.x).unwrap().then(a.y.partial_cmp(&b.y).unwrap()));
+        points.dedup();
+        if points.len() < 3 { return Self::new(points); }
+
+        let mut hull = half_hull(points.iter().copied());
+        let mut upper = half_hull(points.iter().rev().copied());
+        hull.pop();
+        upper.pop();
+        hull.extend(upper);
+
+        Self::new(hull)
+    }
+
+    pub fn from_aabb(aabb: &Aabb) -> Self {
+        let (min, max) = (aabb.min(), aabb.max());
+
+        Self::new(vec![
+            min,
+            Vec2::new(max.x, min.y),
+            max,
+            Vec2::new(min.x, max.y),
+        ])
+    }
+
+    pub fn vertices(&self) -> &[Vec2] {
+        &self.vertices
+    }
+
+    pub fn aabb(&self) -> Aabb {
+        if self.vertices.is_empty() { return Aabb::new(Vec2::ZERO, Vec2::ZERO); }
+
+        let min = self.vertices.iter().fold(Vec2::splat(f32::INFINITY), |min, &v| min.min(v));
+        let max = self.vertices.iter().fold(Vec2::splat(f32::NEG_INFINITY), |max, &v| max.max(v));
+
+        Aabb::from_min_max(min, max)
+    }
+
+    /// Edges as `(start, end, outward normal)`.
+    pub fn edges(&self) -> impl Iterator<Item = (Vec2, Vec2, Vec2)> + '_ {
+        let count = self.vertices.len();
+        let area: f32 = (0..count)
+            .map(|i| self.vertices[i].perp_dot(self.vertices[(i + 1) % count]))
+            .sum();
+        let winding = if area < 0.0 { -1.0 } else { 1.0 };
+
+        (0..count)
+            .filter(move |_| count > 1)
+            .map(move |i| (self.vertices[i], self.vertices[(i + 1) % count]))
+            .map(move |(a, b)| (a, b, Vec2::new(b.y - a.y, a.x - b.x).normalize_or_zero() * winding))
+    }
+
+    /// Closest point of the polygon to `point`, and whether `point` is inside it.
+    pub fn closest_point(&self, point: Vec2) -> (Vec2, bool) {
+        let mut is_inside = self.vertices.len() > 2;
+        let mut closest = point;
+        let mut distance = f32::INFINITY;
+
+        for (a, b, normal) in self.edges() {
+            if (point - a).dot(normal) > 0.0 { is_inside = false; }
+
+            let edge = b - a;
+            let t = ((point - a).dot(edge) / edge.length_squared().max(f32::EPSILON)).clamp(0.0, 1.0);
+            let candidate = a + edge * t;
+            if candidate.distance_squared(point) < distance {
+                distance = candidate.distance_squared(point);
+                closest = candidate;
+            }
+        }
+
+        if self.vertices.len() == 1 { closest = self.vertices[0]; }
+        (closest, is_inside)
+    }
+
+    /// Normalized edge normals, usable as separating axes.
+    pub fn axes(&self) -> impl Iterator<Item = Vec2> + '_ {
+        let count = self.vertices.len();
+
+        (0..count)
+            .filter(move |_| count > 1)
+            .map(move |i| self.vertices[(i + 1) % count] - self.vertices[i])
+            .map(|edge| Vec2::new(edge.y, -edge.x).normalize_or_zero())
+            .filter(|axis| *axis != Vec2::ZERO)
+    }
+
+    pub fn project(&self, axis: Vec2) -> (f32, f32) {
+        self.vertices.iter().fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), v| {
+            let d = v.dot(axis);
+            (min.min(d), max.max(d))
+        })
+    }
+
+    pub fn is_overlapping(&self, other: &Polygon) -> bool {
+        self.axes().chain(other.axes()).all(|axis| {
+            let (a_min, a_max) = self.project(axis);
+            let (b_min, b_max) = other.project(axis);
+
+            a_min < b_max && a_max > b_min
+        })
+    }
+
+    /// Swept separating axis test of `self` moving by `motion` against `other`.
+    pub fn sweep_test(&self, other: &Polygon, motion: Vec2) -> Option<Hit> {
+        let mut t_enter = f32::NEG_INFINITY;
+        let mut t_exit = f32::INFINITY;
+        let mut normal = Vec2::ZERO;
+        let mut normal_speed = f32::INFINITY;
+
+        // without an axis nothing tells the polygons apart
+        let mut axes = self.axes().chain(other.axes()).peekable();
+        axes.peek()?;
+
+        for axis in axes {
+            let (a_min, a_max) = self.project(axis);
+            let (b_min, b_max) = other.project(axis);
+            let speed = motion.dot(axis);
+
+            if speed == 0.0 {
+                if a_max <= b_min || a_min >= b_max { return None; }
+                continue;
+            }
+
+            let t0 = (b_min - a_max) / speed;
+            let t1 = (b_max - a_min) / speed;
+            let (near, far) = if t0 < t1 { (t0, t1) } else { (t1, t0) };
+
+            // on a vertex to vertex tie, keep the axis that deflects
+            // the motion the least so bodies slide instead of snagging
+            let is_tie = (near - t_enter).abs() <= TIE_TOLERANCE;
+            if (near > t_enter && !is_tie) || (is_tie && speed.abs() < normal_speed) {
+                t_enter = t_enter.max(near);
+                normal = if speed > 0.0 { -axis } else { axis };
+                normal_speed = speed.abs();
+            }
+            t_exit = t_exit.min(far);
+        }
+
+        if t_enter > t_exit || t_enter >= 1.0 || t_exit <= 0.0 {
+            return None;
+        }
+
+        Some(Hit {
+            time: t_enter.max(0.0),
+            normal,
+        })
+    }
+}
+
+fn half_hull(points: impl Iterator<Item = Vec2>) -> Vec<Vec2> {
+    let mut hull: Vec<Vec2> = Vec::new();
+
+    for point in points {
+        while hull.len() >= 2
+            && (hull[hull.len() - 1] - hull[hull.len() - 2]).perp_dot(point - hull[hull.len() - 2]) <= 0.0 {
+            hull.pop();
+        }
+        hull.push(point);
+    }
+
+    hull
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::collision::{ Aabb, Polygon };
+    use bevy::math::Vec2;
+
+    #[test]
+    fn test_sweep_slope() {
+        // floor rising to the right, hypotenuse from (0, 0) to (4, 4)
+        let slope = Polygon::new(vec![Vec2::new(4.0, 0.0), Vec2::new(0.0, 0.0), Vec2::new(4.0, 4.0)]);
+        let a = Polygon::from_aabb(&Aabb::new(Vec2::splat(1.0), Vec2::new(1.0, 6.0)));
+
+        let hit = a.sweep_test(&slope, Vec2::new(0.0, -4.0)).unwrap();
+
+        assert!((hit.time - 0.75).abs() < 0.0001);
+        assert!((hit.normal - Vec2::new(-1.0, 1.0).normalize()).length() < 0.0001);
+    }
+
+    #[test]
+    fn test_convex_hull() {
+        let points = vec![Vec2::ZERO, Vec2::new(2.0, 0.0), Vec2::new(1.0, 1.0), Vec2::new(2.0, 2.0), Vec2::new(0.0, 2.0)];
+
+        let hull = Polygon::convex_hull(points);
+
+        assert_eq!(hull.vertices(), &[Vec2::ZERO, Vec2::new(2.0, 0.0), Vec2::new(2.0, 2.0), Vec2::new(0.0, 2.0)]);
+    }
+
+    #[test]
+    fn test_overlap() {
+        let slope = Polygon::new(vec![Vec2::new(4.0, 0.0), Vec2::new(0.0, 0.0), Vec2::new(4.0, 4.0)]);
+
+        assert!(!Polygon::from_aabb(&Aabb::new(Vec2::splat(0.5), Vec2::new(1.0, 3.0))).is_overlapping(&slope));
+        assert!(Polygon::from_aabb(&Aabb::new(Vec2::splat(0.5), Vec2::new(3.0, 1.0))).is_overlapping(&slope));
+    }
+}
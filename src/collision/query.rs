@@ -0,0 +1,480 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use crate::collision::{ external_normal, push_collision, slide_statics, solve_kinematic, SolverSettings, SLIDE_SKIN };
+use crate::collision::{ Aabb, Ball, Collider, Collisions, Contact, Hit, Ray, RayHit, SpatialIndex };
+use crate::components::{ CollisionLayers, KinematicBody, RotationMode, Sensor, Shape, StaticBody };
+
+/// Which bodies a `SpatialQuery` can report.
+#[derive(Clone, Debug, Default)]
+pub struct QueryFilter {
+    /// Only bodies that are members of a layer these filter for are reported.
+    pub layers: CollisionLayers,
+    /// When not empty, only these entities are reported.
+    pub included: Vec<Entity>,
+    pub excluded: Vec<Entity>,
+}
+
+impl QueryFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_layers(mut self, layers: CollisionLayers) -> Self {
+        self.layers = layers;
+        self
+    }
+
+    pub fn with_included(mut self, entity: Entity) -> Self {
+        self.included.push(entity);
+        self
+    }
+
+    pub fn with_excluded(mut self, entity: Entity) -> Self {
+        self.excluded.push(entity);
+        self
+    }
+}
+
+/// `Hit` of a shape cast against a body.
+#[derive(Clone, Copy, Debug)]
+pub struct ShapeHit {
+    pub entity: Entity,
+    pub time: f32,
+    pub normal: Vec2,
+    /// World space position of the shape when it hits.
+    pub position: Vec2,
+    /// Distance travelled by the shape before it hits.
+    pub distance: f32,
+}
+
+impl ShapeHit {
+    pub fn new(entity: Entity, position: Vec2, motion: Vec2, hit: Hit) -> Self {
+        Self {
+            entity,
+            time: hit.time,
+            normal: hit.normal,
+            position: position + motion * hit.time,
+            distance: motion.length() * hit.time,
+        }
+    }
+}
+
+/// Result of `SpatialQuery::move_and_slide` and `SpatialQuery::move_and_collide`.
+#[derive(Clone, Debug, Default)]
+pub struct Movement {
+    /// Motion the body actually did.
+    pub motion: Vec2,
+    /// Part of the requested motion that got blocked.
+    pub remaining: Vec2,
+    pub contacts: Vec<Contact>,
+}
+
+type Body = (Option<&'static KinematicBody>, Option<&'static StaticBody>, &'static GlobalTransform);
+type ChangedBody = (Without<Sensor>, Or<(Changed<StaticBody>, Changed<KinematicBody>, Changed<GlobalTransform>)>);
+
+/// Immediate queries against every `StaticBody` and `KinematicBody`, usable
+/// from any system that doesn't need mutable access to the bodies.
+///
+/// Bodies are tested against their current `GlobalTransform`. Candidates
+/// come from the `SpatialIndex` as of the last `PhysicsStage`, plus the
+/// bodies added or changed since the system last ran, so bodies spawned
+/// or moved in between are found too. Sensors are never reported.
+#[derive(SystemParam)]
+pub struct SpatialQuery<'w, 's> {
+    bodies: Query<'w, 's, Body, Without<Sensor>>,
+    sensors: Query<'w, 's, Entity, (With<KinematicBody>, With<Sensor>)>,
+    changed: Query<'w, 's, Entity, ChangedBody>,
+    layers: Query<'w, 's, &'static CollisionLayers>,
+    index: Res<'w, SpatialIndex>,
+    settings: Res<'w, SolverSettings>,
+}
+
+impl<'w, 's> SpatialQuery<'w, 's> {
+    /// Closest body hit by the ray from `origin` to `origin + direction`.
+    pub fn cast_ray(&self, origin: Vec2, direction: Vec2, filter: &QueryFilter) -> Option<RayHit> {
+        let ray = Ray::new(direction, origin);
+
+        self.ray_hits(&ray, filter).min_by(|a, b| a.time.partial_cmp(&b.time).unwrap())
+    }
+
+    /// Every body hit by the ray, closest first.
+    pub fn cast_ray_all(&self, origin: Vec2, direction: Vec2, filter: &QueryFilter) -> Vec<RayHit> {
+        let ray = Ray::new(direction, origin);
+
+        let mut hits: Vec<RayHit> = self.ray_hits(&ray, filter).collect();
+        hits.sort_by(|a, b| a.time.partial_cmp(&b.time).unwrap());
+
+        hits
+    }
+
+    /// Bodies overlapping `aabb`.
+    pub fn overlap_aabb(&self, aabb: Aabb, filter: &QueryFilter) -> Vec<Entity> {
+        self.overlap(&Collider::Aabb(aabb), filter)
+    }
+
+    /// Bodies containing `point`.
+    pub fn overlap_point(&self, point: Vec2, filter: &QueryFilter) -> Vec<Entity> {
+        self.overlap(&Collider::Ball(Ball::new(point, 0.0)), filter)
+    }
+
+    /// First body hit by `shape` moving from `position` by `motion`.
+    ///
+    /// Like in `solve`, edges shared by still rectangles are never hit.
+    pub fn cast_shape(&self, shape: &Shape, position: Vec2, motion: Vec2, filter: &QueryFilter) -> Option<ShapeHit> {
+        let (a_box, hits) = self.shape_hits(shape, position, motion, filter);
+
+        hits.into_iter().find_map(|hit| self.external_hit(&a_box, motion, hit, filter))
+    }
+
+    /// Every body hit by `shape` moving from `position` by `motion`, closest first.
+    pub fn cast_shape_all(&self, shape: &Shape, position: Vec2, motion: Vec2, filter: &QueryFilter) -> Vec<ShapeHit> {
+        let (a_box, hits) = self.shape_hits(shape, position, motion, filter);
+
+        hits.into_iter().filter_map(|hit| self.external_hit(&a_box, motion, hit, filter)).collect()
+    }
+
+    /// Moves the `KinematicBody` of `entity` by `motion` right away, sliding
+    /// along what it hits the same way `solve` does.
+    ///
+    /// `transform` is the one of `entity`, taken as its global transform,
+    /// and gets the motion done added to it. Other kinematics are expected
+    /// to do their own `motion` this frame. Sensors go through everything
+    /// without contacts, like in `solve`. Returns `None` when `entity` has
+    /// no `KinematicBody`.
+    pub fn move_and_slide(&self, entity: Entity, transform: &mut Transform, motion: Vec2) -> Option<Movement> {
+        self.move_body(entity, transform, motion, true)
+    }
+
+    /// Like `move_and_slide`, but stops the body at the first body it hits
+    /// instead of sliding along it.
+    pub fn move_and_collide(&self, entity: Entity, transform: &mut Transform, motion: Vec2) -> Option<Movement> {
+        self.move_body(entity, transform, motion, false)
+    }
+
+    fn move_body(&self, entity: Entity, transform: &mut Transform, motion: Vec2, slide: bool) -> Option<Movement> {
+        if self.sensors.contains(entity) {
+            return Some(Self::apply_motion(transform, motion, motion, Vec::new()));
+        }
+
+        let (a_body, a_col) = self.kinematic(entity, transform)?;
+        let filter = self.body_filter(entity);
+        // bodies only hit each other when both filter for the other one
+        let visible = |b_ent: Entity| self.is_visible(b_ent, &filter) && self.layers_of(b_ent).filters_for(&filter.layers);
+
+        let candidates = |region: &Aabb| self.static_candidates(region, &visible);
+        let max_slides = if slide { self.settings.max_slides } else { 1 };
+        let (mut a_motion, mut contacts) = slide_statics(a_body, &a_col, motion, max_slides, None, candidates);
+
+        'kinematics: for col in self.kinematic_candidates(&a_col, a_motion, &visible).iter() {
+            for &b_ent in col.entities.iter() {
+                let (b_col, b_motion) = match self.bodies.get(b_ent) {
+                    Ok((Some(b_body), _, b_trans)) => (Collider::from_shape(&b_body.shape, b_trans, b_body.rotation_mode), b_body.total_motion()),
+                    _ => continue,
+                };
+
+                if slide {
+                    contacts.extend(solve_kinematic(&a_col, &mut a_motion, b_ent, &b_col, b_motion));
+                } else if let Some(contact) = Self::collide_kinematic(&a_col, &mut a_motion, b_ent, &b_col, b_motion) {
+                    // hit before reaching the static that stopped the body, if any
+                    contacts = vec![contact];
+                    break 'kinematics;
+                }
+            }
+        }
+
+        Some(Self::apply_motion(transform, motion, a_motion, contacts))
+    }
+
+    /// Stops `a_motion` where `a_col` first touches `b_col` moving by `b_motion`.
+    fn collide_kinematic(a_col: &Collider, a_motion: &mut Vec2, b_ent: Entity, b_col: &Collider, b_motion: Vec2) -> Option<Contact> {
+        let (hit, own_shape, shape) = a_col.sweep_parts(b_col, *a_motion - b_motion)?;
+        if hit.normal == Vec2::ZERO {
+            *a_motion *= hit.time;
+            return None;
+        }
+
+        *a_motion = *a_motion * hit.time + hit.normal * SLIDE_SKIN;
+        Some(Contact {
+            entity: b_ent,
+            normal: hit.normal,
+            shape,
+            own_shape,
+        })
+    }
+
+    /// `visible` statics in `region`, as `slide_statics` takes them.
+    fn static_candidates(&self, region: &Aabb, visible: &impl Fn(Entity) -> bool) -> Vec<(Entity, &StaticBody, Collider)> {
+        self.query_aabb(region).into_iter()
+            .filter(|&b_ent| visible(b_ent))
+            .filter_map(|b_ent| match self.bodies.get(b_ent) {
+                Ok((_, Some(b_body), b_trans)) => Some((b_ent, b_body, Collider::from_shape(&b_body.shape, b_trans, b_body.rotation_mode))),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Bodies whose box may overlap `aabb`, some of them not being in the index yet.
+    fn query_aabb(&self, aabb: &Aabb) -> Vec<Entity> {
+        let changed = self.changed.iter().filter(|&entity| self.broad_aabb(entity).is_some_and(|b_box| b_box.is_overlapping(aabb)));
+
+        Self::merge(self.index.query_aabb(aabb), changed)
+    }
+
+    fn query_ray(&self, ray: &Ray) -> Vec<Entity> {
+        let changed = self.changed.iter().filter(|&entity| self.broad_aabb(entity).is_some_and(|b_box| ray.overlaps_aabb(&b_box)));
+
+        Self::merge(self.index.query_ray(ray), changed)
+    }
+
+    fn merge(mut entities: Vec<Entity>, others: impl Iterator<Item = Entity>) -> Vec<Entity> {
+        entities.extend(others);
+        entities.sort();
+        entities.dedup();
+        entities
+    }
+
+    /// Box of `entity` the way `update_index` stores it.
+    fn broad_aabb(&self, entity: Entity) -> Option<Aabb> {
+        let motion = match self.bodies.get(entity).ok()? {
+            (Some(body), _, _) => body.total_motion(),
+            (_, Some(body), _) => body.motion,
+            _ => return None,
+        };
+
+        Some(self.collider(entity)?.aabb().get_broad(motion))
+    }
+
+    fn kinematic(&self, entity: Entity, transform: &Transform) -> Option<(&KinematicBody, Collider)> {
+        let body = self.bodies.get(entity).ok()?.0?;
+        let collider = Collider::from_shape(&body.shape, &GlobalTransform::from(*transform), body.rotation_mode);
+
+        Some((body, collider))
+    }
+
+    /// Kinematics hit by `a_col`, grouped by time of impact like in `narrowphase`.
+    fn kinematic_candidates(&self, a_col: &Collider, motion: Vec2, visible: &impl Fn(Entity) -> bool) -> Vec<Collisions> {
+        let mut collisions = Vec::new();
+
+        for b_ent in self.query_aabb(&a_col.aabb().get_broad(motion)) {
+            if !visible(b_ent) { continue; }
+
+            let b_body = match self.bodies.get(b_ent) {
+                Ok((Some(body), _, _)) => body,
+                _ => continue,
+            };
+
+            // both bodies are moving, so sweep with the motion relative to the other one
+            if let Some(hit) = self.collider(b_ent).and_then(|b_col| a_col.sweep_test(&b_col, motion - b_body.total_motion())) {
+                push_collision(&mut collisions, hit.time, b_ent);
+            }
+        }
+
+        collisions.sort_by(|a, b| (a.time).partial_cmp(&b.time).unwrap());
+        collisions
+    }
+
+    fn apply_motion(transform: &mut Transform, motion: Vec2, done: Vec2, contacts: Vec<Contact>) -> Movement {
+        transform.translation.x += done.x;
+        transform.translation.y += done.y;
+
+        Movement {
+            motion: done,
+            remaining: motion - done,
+            contacts,
+        }
+    }
+
+    /// What the body of `entity` collides with.
+    fn body_filter(&self, entity: Entity) -> QueryFilter {
+        QueryFilter::new()
+            .with_layers(self.layers_of(entity))
+            .with_excluded(entity)
+    }
+
+    fn layers_of(&self, entity: Entity) -> CollisionLayers {
+        self.layers.get(entity).copied().unwrap_or_default()
+    }
+
+    fn ray_hits<'a>(&'a self, ray: &'a Ray, filter: &'a QueryFilter) -> impl Iterator<Item = RayHit> + 'a {
+        self.query_ray(ray).into_iter()
+            .filter(|&entity| self.is_visible(entity, filter))
+            .filter_map(|entity| Some(RayHit::new(entity, ray, ray.intersect(&self.collider(entity)?)?)))
+    }
+
+    /// Box of `shape` at `position`, and every body it hits sorted by time.
+    fn shape_hits(&self, shape: &Shape, position: Vec2, motion: Vec2, filter: &QueryFilter) -> (Aabb, Vec<ShapeHit>) {
+        let transform = GlobalTransform::from_translation(position.extend(0.0));
+        let a_col = Collider::from_shape(shape, &transform, RotationMode::Ignore);
+
+        let mut hits: Vec<ShapeHit> = self.query_aabb(&a_col.aabb().get_broad(motion)).into_iter()
+            .filter(|&entity| self.is_visible(entity, filter))
+            .filter_map(|entity| {
+                let hit = a_col.sweep_test(&self.collider(entity)?, motion)?;
+                Some(ShapeHit::new(entity, position, motion, hit))
+            })
+            .collect();
+        hits.sort_by(|a, b| a.time.partial_cmp(&b.time).unwrap());
+
+        (a_col.aabb(), hits)
+    }
+
+    /// `hit` without the parts of its normal on internal edges, `None` when nothing is left.
+    fn external_hit(&self, a_box: &Aabb, motion: Vec2, mut hit: ShapeHit, filter: &QueryFilter) -> Option<ShapeHit> {
+        let b_box = match (self.bodies.get(hit.entity), self.collider(hit.entity)) {
+            (Ok((None, Some(b_body), _)), Some(Collider::Aabb(b_box))) if b_body.motion == Vec2::ZERO => b_box,
+            _ => return Some(hit),
+        };
+        let a_box = Aabb::new(a_box.extents(), a_box.position() + motion * hit.time);
+
+        hit.normal = external_normal(hit.entity, &b_box, hit.normal, &a_box, &|region: &Aabb| self.static_candidates(region, &|b_ent| self.is_visible(b_ent, filter)))?;
+        Some(hit)
+    }
+
+    fn overlap(&self, a_col: &Collider, filter: &QueryFilter) -> Vec<Entity> {
+        self.query_aabb(&a_col.aabb()).into_iter()
+            .filter(|&entity| self.is_visible(entity, filter))
+            .filter(|&entity| self.collider(entity).is_some_and(|b_col| a_col.is_overlapping(&b_col)))
+            .collect()
+    }
+
+    fn collider(&self, entity: Entity) -> Option<Collider> {
+        match self.bodies.get(entity) {
+            Ok((Some(body), _, trans)) => Some(Collider::from_shape(&body.shape, trans, body.rotation_mode)),
+            Ok((_, Some(body), trans)) => Some(Collider::from_shape(&body.shape, trans, body.rotation_mode)),
+            _ => None,
+        }
+    }
+
+    fn is_visible(&self, entity: Entity, filter: &QueryFilter) -> bool {
+        (filter.included.is_empty() || filter.included.contains(&entity))
+        && !filter.excluded.contains(&entity)
+        && filter.layers.filters_for(&self.layers_of(entity))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::SystemState;
+    use bevy::prelude::*;
+    use crate::collision::{ Aabb, Collider, QueryFilter, SolverSettings, SpatialIndex, SpatialQuery };
+    use crate::components::{ KinematicBody, Rectangle, RotationMode, Sensor, Shape, StaticBody };
+
+    fn spawn_box(world: &mut World, position: Vec2) -> Entity {
+        let body = StaticBody::new(Rectangle::new().with_size(Vec2::splat(2.0)));
+        let transform = GlobalTransform::from_translation(position.extend(0.0));
+        let aabb = Collider::from_shape(&body.shape, &transform, RotationMode::Ignore).aabb();
+
+        let entity = world.spawn().insert(body).insert(transform).id();
+        world.resource_mut::<SpatialIndex>().insert(entity, aabb);
+        entity
+    }
+
+    #[test]
+    fn test_queries() {
+        let mut world = World::new();
+        world.init_resource::<SpatialIndex>();
+        world.init_resource::<SolverSettings>();
+        let a = spawn_box(&mut world, Vec2::new(4.0, 0.0));
+        let b = spawn_box(&mut world, Vec2::new(8.0, 0.0));
+
+        let mut state: SystemState<SpatialQuery> = SystemState::new(&mut world);
+        let query = state.get_mut(&mut world);
+        let filter = QueryFilter::new();
+
+        let hits = query.cast_ray_all(Vec2::ZERO, Vec2::new(10.0, 0.0), &filter);
+        assert_eq!(hits.iter().map(|hit| hit.entity).collect::<Vec<_>>(), vec![a, b]);
+        assert_eq!(hits[1].point, Vec2::new(7.0, 0.0));
+        assert_eq!(hits[1].distance, 7.0);
+        assert_eq!(query.cast_ray(Vec2::ZERO, Vec2::new(10.0, 0.0), &filter).unwrap().entity, a);
+        assert_eq!(query.cast_ray(Vec2::ZERO, Vec2::new(10.0, 0.0), &filter.clone().with_excluded(a)).unwrap().entity, b);
+        assert!(query.cast_ray(Vec2::ZERO, Vec2::new(10.0, 0.0), &filter.clone().with_included(a).with_excluded(a)).is_none());
+
+        assert_eq!(query.overlap_point(Vec2::new(8.5, 0.5), &filter), vec![b]);
+        assert_eq!(query.overlap_aabb(Aabb::new(Vec2::splat(1.0), Vec2::new(6.0, 5.0)), &filter), vec![]);
+
+        let shape = Shape::from(Rectangle::new().with_size(Vec2::splat(2.0)));
+        let hit = query.cast_shape(&shape, Vec2::ZERO, Vec2::new(4.0, 0.0), &filter).unwrap();
+        assert_eq!(hit.entity, a);
+        assert_eq!(hit.time, 0.5);
+        assert_eq!(hit.position, Vec2::new(2.0, 0.0));
+
+        // starting inside a body hits it right away
+        let hits = query.cast_shape_all(&shape, Vec2::new(7.0, 0.0), Vec2::new(-4.0, 0.0), &filter);
+        assert_eq!(hits.iter().map(|hit| (hit.entity, hit.time)).collect::<Vec<_>>(), vec![(b, 0.0), (a, 0.25)]);
+
+        // a floor of two boxes, the shape slightly sunk in it never hits their shared edge
+        let floor = spawn_box(&mut world, Vec2::new(0.0, -12.0));
+        spawn_box(&mut world, Vec2::new(2.0, -12.0));
+        let query = state.get_mut(&mut world);
+        let hits = query.cast_shape_all(&shape, Vec2::new(-0.5, -10.005), Vec2::new(3.0, -0.5), &filter);
+        assert_eq!(hits.iter().map(|hit| (hit.entity, hit.normal)).collect::<Vec<_>>(), vec![(floor, Vec2::Y)]);
+
+        // bodies spawned or moved since the index got updated are found where they are now
+        let body = StaticBody::new(Rectangle::new().with_size(Vec2::splat(2.0)));
+        let spawned = world.spawn().insert(body).insert(GlobalTransform::from_xyz(0.0, 20.0, 0.0)).id();
+        world.get_mut::<GlobalTransform>(a).unwrap().translation = Vec3::new(0.0, -20.0, 0.0);
+        let query = state.get_mut(&mut world);
+        assert_eq!(query.cast_ray(Vec2::ZERO, Vec2::new(0.0, 30.0), &filter).unwrap().entity, spawned);
+        assert_eq!(query.overlap_point(Vec2::new(0.0, -20.0), &filter), vec![a]);
+        assert!(query.overlap_point(Vec2::new(4.0, 0.0), &filter).is_empty());
+    }
+
+    #[test]
+    fn test_move() {
+        let mut world = World::new();
+        world.init_resource::<SpatialIndex>();
+        world.init_resource::<SolverSettings>();
+        let floor = spawn_box(&mut world, Vec2::new(0.0, -2.0));
+        let player = world.spawn()
+            .insert(KinematicBody::new(Rectangle::new().with_size(Vec2::splat(2.0))))
+            .insert(GlobalTransform::identity())
+            .id();
+
+        let mut state: SystemState<SpatialQuery> = SystemState::new(&mut world);
+        let query = state.get_mut(&mut world);
+
+        let mut transform = Transform::from_xyz(0.0, 2.0, 0.0);
+        let movement = query.move_and_slide(player, &mut transform, Vec2::new(2.0, -4.0)).unwrap();
+        assert!((transform.translation.truncate() - Vec2::new(2.0, 0.0)).length() < 0.01);
+        assert!((movement.remaining - Vec2::new(0.0, -2.0)).length() < 0.01);
+        assert_eq!(movement.contacts[0].entity(), floor);
+        assert_eq!(movement.contacts[0].normal(), Vec2::Y);
+
+        let mut transform = Transform::from_xyz(0.0, 2.0, 0.0);
+        let movement = query.move_and_collide(player, &mut transform, Vec2::new(2.0, -4.0)).unwrap();
+        assert!((transform.translation.truncate() - Vec2::new(1.0, 0.0)).length() < 0.01);
+        assert!((movement.remaining - Vec2::new(1.0, -2.0)).length() < 0.01);
+        assert_eq!(movement.contacts.len(), 1);
+
+        // a body moving out of the way is only hit once caught up with
+        let mut body = KinematicBody::new(Rectangle::new().with_size(Vec2::splat(2.0)));
+        body.motion = Vec2::new(2.0, 0.0);
+        let other = world.spawn().insert(body).insert(GlobalTransform::from_xyz(4.0, 10.0, 0.0)).id();
+        world.resource_mut::<SpatialIndex>().insert(other, Aabb::new(Vec2::new(2.0, 1.0), Vec2::new(5.0, 10.0)));
+
+        let query = state.get_mut(&mut world);
+        let mut transform = Transform::from_xyz(0.0, 10.0, 0.0);
+        let movement = query.move_and_collide(player, &mut transform, Vec2::new(3.0, 0.0)).unwrap();
+        assert!(movement.contacts.is_empty());
+
+        let mut transform = Transform::from_xyz(0.0, 10.0, 0.0);
+        let movement = query.move_and_slide(player, &mut transform, Vec2::new(5.0, 0.0)).unwrap();
+        assert!((transform.translation.truncate() - Vec2::new(4.0, 10.0)).length() < 0.001);
+        assert_eq!(movement.contacts[0].entity(), other);
+
+        // colliding stops where it catches up with the body, without sliding along it
+        let mut transform = Transform::from_xyz(0.0, 10.0, 0.0);
+        let movement = query.move_and_collide(player, &mut transform, Vec2::new(5.0, 1.0)).unwrap();
+        assert!((transform.translation.truncate() - Vec2::new(10.0, 32.0) / 3.0).length() < 0.01);
+        assert_eq!(movement.contacts[0].entity(), other);
+        assert_eq!(movement.contacts[0].normal(), -Vec2::X);
+
+        // sensors go through everything
+        world.entity_mut(player).insert(Sensor);
+        let query = state.get_mut(&mut world);
+        let mut transform = Transform::from_xyz(0.0, 2.0, 0.0);
+        let movement = query.move_and_collide(player, &mut transform, Vec2::new(2.0, -4.0)).unwrap();
+        assert_eq!(transform.translation.truncate(), Vec2::new(2.0, -2.0));
+        assert!(movement.contacts.is_empty());
+    }
+}
\ No newline at end of file
@@ -1,5 +1,5 @@
-use bevy::prelude::{ GlobalTransform, Vec2 };
-use crate::{ collision::Aabb, components::Raycast };
+use bevy::prelude::{ Entity, GlobalTransform, Vec2 };
+use crate::{ collision::{ sweep_point_circle, Aabb, Ball, Collider, Obb, Polygon, Stadium }, components::Raycast, utils::EPSILON };
 
 #[derive(Clone, Copy, Debug)]
 pub struct Ray {
@@ -22,6 +22,79 @@ impl Ray {
         )
     }
 
+    pub fn intersect(self, other: &Collider) -> Option<Hit> {
+        match other {
+            Collider::Aabb(aabb) => self.intersect_aabb(*aabb),
+            Collider::Obb(obb) => self.intersect_obb(obb),
+            Collider::Polygon(polygon) => self.intersect_polygon(polygon),
+            Collider::Ball(ball) => self.intersect_ball(ball),
+            Collider::Stadium(stadium) => self.intersect_stadium(stadium),
+            Collider::Compound(parts) => parts.iter()
+                .filter_map(|part| self.intersect(part))
+                .min_by(|a, b| a.time.partial_cmp(&b.time).unwrap()),
+        }
+    }
+
+    pub fn intersect_stadium(self, other: &Stadium) -> Option<Hit> {
+        Collider::Ball(Ball::new(self.position, 0.0)).sweep_test(&Collider::Stadium(*other), self.direction)
+    }
+
+    pub fn intersect_ball(self, other: &Ball) -> Option<Hit> {
+        if self.position.distance_squared(other.center()) < other.radius().powi(2) {
+            return Some(Hit { time: 0.0, normal: Vec2::ZERO });
+        }
+
+        let time = sweep_point_circle(self.position, self.direction, other.center(), other.radius())?;
+        Some(Hit {
+            time,
+            normal: (self.position + self.direction * time - other.center()).normalize_or_zero(),
+        })
+    }
+
+    pub fn intersect_polygon(self, other: &Polygon) -> Option<Hit> {
+        Polygon::new(vec![self.position]).sweep_test(other, self.direction)
+    }
+
+    /// Whether the ray segment touches `other`, edges included.
+    pub fn overlaps_aabb(&self, other: &Aabb) -> bool {
+        let mut t_min: f32 = 0.0;
+        let mut t_max: f32 = 1.0;
+
+        for axis in 0..2 {
+            let (min, max) = (other.min()[axis], other.max()[axis]);
+
+            if self.direction[axis].abs() < EPSILON {
+                if self.position[axis] < min || self.position[axis] > max { return false; }
+                continue;
+            }
+
+            let inv_dir = 1.0 / self.direction[axis];
+            let t1 = (min - self.position[axis]) * inv_dir;
+            let t2 = (max - self.position[axis]) * inv_dir;
+
+            t_min = t_min.max(t1.min(t2));
+            t_max = t_max.min(t1.max(t2));
+            if t_min > t_max { return false; }
+        }
+
+        true
+    }
+
+    /// Intersects the ray turned into the space of `other` with it as an `Aabb`.
+    pub fn intersect_obb(self, other: &Obb) -> Option<Hit> {
+        let [x, y] = other.axes();
+        let local = Ray::new(
+            Vec2::new(self.direction.dot(x), self.direction.dot(y)),
+            other.to_local(self.position),
+        );
+        let hit = local.intersect_aabb(Aabb::new(other.extents(), Vec2::ZERO))?;
+
+        Some(Hit {
+            time: hit.time,
+            normal: x * hit.normal.x + y * hit.normal.y,
+        })
+    }
+
     // https://noonat.github.io/intersect/#axis-aligned-bounding-boxes
     pub fn intersect_aabb(self, other: Aabb) -> Option<Hit> {
         let mut hit = Hit::default();
@@ -61,9 +134,33 @@ pub struct Hit {
     pub normal: Vec2,
 }
 
+/// `Hit` of a ray against a body.
+#[derive(Clone, Copy, Debug)]
+pub struct RayHit {
+    pub entity: Entity,
+    pub time: f32,
+    pub normal: Vec2,
+    /// World space position of the hit.
+    pub point: Vec2,
+    /// Distance from the start of the ray to `point`.
+    pub distance: f32,
+}
+
+impl RayHit {
+    pub fn new(entity: Entity, ray: &Ray, hit: Hit) -> Self {
+        Self {
+            entity,
+            time: hit.time,
+            normal: hit.normal,
+            point: ray.position + ray.direction * hit.time,
+            distance: ray.direction.length() * hit.time,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::collision::{ Aabb, Ray };
+    use crate::collision::{ Aabb, Polygon, Ray };
     use bevy::math::Vec2;
 
     #[test]
@@ -84,4 +181,15 @@ mod tests {
         let hit = ray.intersect_aabb(a).unwrap();
         assert_eq!(hit.normal, Vec2::new(1.0, 0.0));
     }
+
+    #[test]
+    fn test_polygon() {
+        let slope = Polygon::new(vec![Vec2::new(4.0, 0.0), Vec2::new(0.0, 0.0), Vec2::new(4.0, 4.0)]);
+        let ray = Ray::new(Vec2::new(0.0, -4.0), Vec2::new(3.0, 5.0));
+
+        let hit = ray.intersect_polygon(&slope).unwrap();
+
+        assert!((hit.time - 0.5).abs() < 0.0001);
+        assert!(hit.normal.x < 0.0 && hit.normal.y > 0.0);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,179 @@
+use bevy::prelude::Entity;
+use bevy::utils::{ HashMap, HashSet };
+use crate::collision::{ Aabb, Broadphase, Ray };
+
+/// Sort and sweep along the x axis.
+///
+/// Endpoints stay sorted between frames, so re-sorting them after bodies
+/// move is a nearly linear insertion sort.
+#[derive(Default)]
+pub struct SweepAndPrune {
+    entries: HashMap<Entity, Aabb>,
+    endpoints: Vec<Endpoint>,
+    /// Width of the widest box, bounding how far before a query its candidates start.
+    max_width: f32,
+    is_dirty: bool,
+}
+
+#[derive(Clone, Copy)]
+struct Endpoint {
+    value: f32,
+    entity: Entity,
+    is_min: bool,
+}
+
+impl SweepAndPrune {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn sort_endpoints(&mut self) {
+        for i in 1..self.endpoints.len() {
+            let mut j = i;
+            while j > 0 && self.endpoints[j - 1].value > self.endpoints[j].value {
+                self.endpoints.swap(j - 1, j);
+                j -= 1;
+            }
+        }
+    }
+
+    /// Entities whose box starts between `min_x` minus the widest box and `max_x`,
+    /// found with a binary search on the sorted endpoints.
+    fn candidates(&self, min_x: f32, max_x: f32) -> impl Iterator<Item = (Entity, &Aabb)> + '_ {
+        let start = self.endpoints.partition_point(|endpoint| endpoint.value < min_x - self.max_width);
+
+        self.endpoints[start..].iter()
+            .take_while(move |endpoint| endpoint.value <= max_x)
+            .filter(|endpoint| endpoint.is_min)
+            .filter_map(|endpoint| self.entries.get(&endpoint.entity).map(|aabb| (endpoint.entity, aabb)))
+    }
+}
+
+impl Broadphase for SweepAndPrune {
+    fn get(&self, entity: Entity) -> Option<Aabb> {
+        self.entries.get(&entity).copied()
+    }
+
+    fn insert(&mut self, entity: Entity, aabb: Aabb) {
+        if self.entries.insert(entity, aabb).is_none() {
+            self.endpoints.push(Endpoint { value: aabb.min().x, entity, is_min: true });
+            self.endpoints.push(Endpoint { value: aabb.max().x, entity, is_min: false });
+        }
+        self.is_dirty = true;
+    }
+
+    fn remove(&mut self, entity: Entity) {
+        if self.entries.remove(&entity).is_some() {
+            self.is_dirty = true;
+        }
+    }
+
+    fn update(&mut self) {
+        if !self.is_dirty { return; }
+
+        let entries = &self.entries;
+        self.endpoints.retain(|endpoint| entries.contains_key(&endpoint.entity));
+        for endpoint in self.endpoints.iter_mut() {
+            let aabb = entries[&endpoint.entity];
+            endpoint.value = if endpoint.is_min { aabb.min().x } else { aabb.max().x };
+        }
+        self.max_width = entries.values().fold(0.0, |width, aabb| width.max(aabb.extents().x * 2.0));
+
+        self.sort_endpoints();
+        self.is_dirty = false;
+    }
+
+    fn query_aabb(&self, aabb: &Aabb) -> Vec<Entity> {
+        self.candidates(aabb.min().x, aabb.max().x)
+            .filter(|(_, other)| other.is_overlapping(aabb))
+            .map(|(entity, _)| entity)
+            .collect()
+    }
+
+    fn query_ray(&self, ray: &Ray) -> Vec<Entity> {
+        let min_x = ray.position.x.min(ray.position.x + ray.direction.x);
+        let max_x = ray.position.x.max(ray.position.x + ray.direction.x);
+
+        self.candidates(min_x, max_x)
+            .filter(|(_, other)| ray.overlaps_aabb(other))
+            .map(|(entity, _)| entity)
+            .collect()
+    }
+
+    fn query_pairs(&self, entities: &[Entity]) -> Vec<(Entity, Entity)> {
+        let entities: HashSet<Entity> = entities.iter().copied().collect();
+        let mut active: Vec<(Entity, Aabb)> = Vec::new();
+        let mut pairs = Vec::new();
+
+        for endpoint in self.endpoints.iter() {
+            let aabb = match self.entries.get(&endpoint.entity) {
+                Some(aabb) => *aabb,
+                None => continue,
+            };
+
+            if !endpoint.is_min {
+                active.retain(|(entity, _)| *entity != endpoint.entity);
+                continue;
+            }
+
+            for &(other, other_box) in active.iter() {
+                if !aabb.is_overlapping(&other_box) { continue; }
+
+                if entities.contains(&endpoint.entity) { pairs.push((endpoint.entity, other)); }
+                if entities.contains(&other) { pairs.push((other, endpoint.entity)); }
+            }
+            active.push((endpoint.entity, aabb));
+        }
+
+        pairs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::collision::{ Aabb, Broadphase, Ray, SweepAndPrune };
+    use bevy::prelude::{ Entity, Vec2 };
+
+    #[test]
+    fn test_query_pairs() {
+        let mut sap = SweepAndPrune::new();
+        let (a, b, c) = (Entity::from_raw(0), Entity::from_raw(1), Entity::from_raw(2));
+
+        sap.insert(a, Aabb::new(Vec2::splat(5.0), Vec2::new(0.0, 0.0)));
+        sap.insert(b, Aabb::new(Vec2::splat(5.0), Vec2::new(8.0, 0.0)));
+        sap.insert(c, Aabb::new(Vec2::splat(5.0), Vec2::new(30.0, 0.0)));
+        sap.update();
+
+        assert_eq!(sap.query_pairs(&[a]), vec![(a, b)]);
+
+        // moving c over a only needs a few swaps to stay sorted
+        sap.insert(c, Aabb::new(Vec2::splat(5.0), Vec2::new(-4.0, 0.0)));
+        sap.update();
+
+        let mut pairs = sap.query_pairs(&[a, c]);
+        pairs.sort();
+        assert_eq!(pairs, vec![(a, b), (a, c), (c, a)]);
+    }
+
+    #[test]
+    fn test_query_aabb() {
+        let mut sap = SweepAndPrune::new();
+        let wide = Entity::from_raw(0);
+
+        sap.insert(wide, Aabb::new(Vec2::new(50.0, 1.0), Vec2::new(0.0, 0.0)));
+        for i in 1..100 {
+            sap.insert(Entity::from_raw(i), Aabb::new(Vec2::splat(1.0), Vec2::new(i as f32 * 4.0 - 200.0, 0.0)));
+        }
+        sap.update();
+
+        // the wide box starts long before the query but still overlaps it
+        let mut found = sap.query_aabb(&Aabb::new(Vec2::splat(1.0), Vec2::new(40.0, 0.0)));
+        found.sort();
+        assert_eq!(found, vec![wide, Entity::from_raw(60)]);
+
+        let ray = Ray::new(Vec2::new(-3.0, 0.0), Vec2::new(30.0, 0.0));
+        let mut found = sap.query_ray(&ray);
+        found.sort();
+        assert_eq!(found, vec![wide, Entity::from_raw(57)]);
+    }
+}
@@ -0,0 +1,67 @@
+use bevy::prelude::Vec2;
+use crate::collision::Aabb;
+
+/// Capsule in world space, a segment grown by `radius`.
+#[derive(Clone, Copy, Debug)]
+pub struct Stadium {
+    center: Vec2,
+    half_segment: Vec2,
+    radius: f32,
+}
+
+impl Stadium {
+    pub fn new(center: Vec2, half_segment: Vec2, radius: f32) -> Self {
+        Self {
+            center,
+            half_segment,
+            radius,
+        }
+    }
+
+    pub fn center(&self) -> Vec2 {
+        self.center
+    }
+
+    pub fn radius(&self) -> f32 {
+        self.radius
+    }
+
+    pub fn half_segment(&self) -> Vec2 {
+        self.half_segment
+    }
+
+    /// Both ends of the inner segment.
+    pub fn segment(&self) -> [Vec2; 2] {
+        [self.center - self.half_segment, self.center + self.half_segment]
+    }
+
+    pub fn aabb(&self) -> Aabb {
+        Aabb::new(self.half_segment.abs() + Vec2::splat(self.radius), self.center)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::collision::{ Aabb, Collider, Stadium };
+    use bevy::math::Vec2;
+
+    #[test]
+    fn test_sweep_aabb() {
+        let a = Collider::Stadium(Stadium::new(Vec2::new(0.0, 6.0), Vec2::new(0.0, 2.0), 1.0));
+        let b = Collider::Aabb(Aabb::new(Vec2::splat(1.0), Vec2::ZERO));
+
+        let hit = a.sweep_test(&b, Vec2::new(0.0, -4.0)).unwrap();
+        assert_eq!(hit.time, 0.5);
+        assert_eq!(hit.normal, Vec2::new(0.0, 1.0));
+    }
+
+    #[test]
+    fn test_round_ledge() {
+        // walking off a ledge, the rounded bottom slides past the corner
+        let a = Collider::Stadium(Stadium::new(Vec2::new(1.5, 4.0), Vec2::new(0.0, 2.0), 1.0));
+        let b = Collider::Aabb(Aabb::new(Vec2::splat(1.0), Vec2::ZERO));
+
+        let hit = a.sweep_test(&b, Vec2::new(0.0, -2.0)).unwrap();
+        assert!(hit.normal.x > 0.0 && hit.normal.y > 0.0);
+    }
+}
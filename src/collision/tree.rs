@@ -0,0 +1,261 @@
+use bevy::prelude::{ Entity, Vec2 };
+use bevy::utils::HashMap;
+use crate::collision::{ Aabb, Broadphase, Ray };
+
+pub const DEFAULT_TREE_MARGIN: f32 = 4.0;
+
+/// Dynamic bounding volume hierarchy.
+///
+/// Leaves store a box fattened by `margin`, so small movements don't
+/// need to restructure the tree.
+pub struct AabbTree {
+    margin: f32,
+
+    nodes: Vec<Node>,
+    free: Vec<usize>,
+    root: Option<usize>,
+    leaves: HashMap<Entity, usize>,
+}
+
+#[derive(Clone, Copy)]
+struct Node {
+    aabb: Aabb,
+    parent: Option<usize>,
+    children: Option<[usize; 2]>,
+    leaf: Option<(Entity, Aabb)>,
+}
+
+impl Default for AabbTree {
+    fn default() -> Self {
+        Self::new(DEFAULT_TREE_MARGIN)
+    }
+}
+
+impl AabbTree {
+    pub fn new(margin: f32) -> Self {
+        Self {
+            margin: margin.abs(),
+
+            nodes: Vec::new(),
+            free: Vec::new(),
+            root: None,
+            leaves: HashMap::default(),
+        }
+    }
+
+    pub fn margin(&self) -> f32 {
+        self.margin
+    }
+
+    fn traverse(&self, node_test: impl Fn(&Aabb) -> bool, leaf_test: impl Fn(&Aabb) -> bool) -> Vec<Entity> {
+        let mut result = Vec::new();
+        let mut stack: Vec<usize> = self.root.into_iter().collect();
+
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            if !node_test(&node.aabb) { continue; }
+
+            match (node.children, node.leaf) {
+                (Some(children), _) => stack.extend(children),
+                (None, Some((entity, aabb))) => if leaf_test(&aabb) { result.push(entity) },
+                (None, None) => continue,
+            }
+        }
+
+        result
+    }
+
+    fn find_sibling(&self, root: usize, aabb: &Aabb) -> usize {
+        let mut index = root;
+
+        while let Some([left, right]) = self.nodes[index].children {
+            let area = self.nodes[index].aabb.perimeter();
+            let combined = self.nodes[index].aabb.merge(aabb).perimeter();
+
+            // cost of pairing with this node vs pushing the leaf further down
+            let cost = 2.0 * combined;
+            let inheritance = 2.0 * (combined - area);
+
+            let child_cost = |child: usize| {
+                let node = &self.nodes[child];
+                let merged = node.aabb.merge(aabb).perimeter();
+                match node.children {
+                    Some(_) => merged - node.aabb.perimeter() + inheritance,
+                    None => merged + inheritance,
+                }
+            };
+            let (cost_left, cost_right) = (child_cost(left), child_cost(right));
+
+            if cost < cost_left && cost < cost_right { break; }
+            index = if cost_left < cost_right { left } else { right };
+        }
+
+        index
+    }
+
+    fn refit(&mut self, mut index: Option<usize>) {
+        while let Some(i) = index {
+            if let Some([left, right]) = self.nodes[i].children {
+                self.nodes[i].aabb = self.nodes[left].aabb.merge(&self.nodes[right].aabb);
+            }
+            index = self.nodes[i].parent;
+        }
+    }
+
+    fn replace_child(&mut self, parent: usize, old: usize, new: usize) {
+        if let Some(children) = self.nodes[parent].children.as_mut() {
+            for child in children.iter_mut() {
+                if *child == old { *child = new; }
+            }
+        }
+    }
+
+    fn allocate(&mut self, node: Node) -> usize {
+        match self.free.pop() {
+            Some(index) => {
+                self.nodes[index] = node;
+                index
+            },
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            },
+        }
+    }
+}
+
+impl Broadphase for AabbTree {
+    fn get(&self, entity: Entity) -> Option<Aabb> {
+        self.leaves.get(&entity).and_then(|&leaf| self.nodes[leaf].leaf).map(|(_, aabb)| aabb)
+    }
+
+    fn insert(&mut self, entity: Entity, aabb: Aabb) {
+        if let Some(&leaf) = self.leaves.get(&entity) {
+            if self.nodes[leaf].aabb.contains(&aabb) {
+                self.nodes[leaf].leaf = Some((entity, aabb));
+                return;
+            }
+            self.remove(entity);
+        }
+
+        let fat = Aabb::new(aabb.extents() + Vec2::splat(self.margin), aabb.position());
+        let leaf = self.allocate(Node {
+            aabb: fat,
+            parent: None,
+            children: None,
+            leaf: Some((entity, aabb)),
+        });
+        self.leaves.insert(entity, leaf);
+
+        let root = match self.root {
+            Some(root) => root,
+            None => {
+                self.root = Some(leaf);
+                return;
+            },
+        };
+
+        let sibling = self.find_sibling(root, &fat);
+        let old_parent = self.nodes[sibling].parent;
+        let parent = self.allocate(Node {
+            aabb: self.nodes[sibling].aabb.merge(&fat),
+            parent: old_parent,
+            children: Some([sibling, leaf]),
+            leaf: None,
+        });
+
+        match old_parent {
+            Some(old_parent) => self.replace_child(old_parent, sibling, parent),
+            None => self.root = Some(parent),
+        }
+        self.nodes[sibling].parent = Some(parent);
+        self.nodes[leaf].parent = Some(parent);
+
+        self.refit(old_parent);
+    }
+
+    fn remove(&mut self, entity: Entity) {
+        let leaf = match self.leaves.remove(&entity) {
+            Some(leaf) => leaf,
+            None => return,
+        };
+
+        let parent = match self.nodes[leaf].parent {
+            Some(parent) => parent,
+            None => {
+                self.root = None;
+                self.free.push(leaf);
+                return;
+            },
+        };
+
+        let sibling = match self.nodes[parent].children {
+            Some([left, right]) => if left == leaf { right } else { left },
+            None => unreachable!(),
+        };
+        let grandparent = self.nodes[parent].parent;
+
+        match grandparent {
+            Some(grandparent) => self.replace_child(grandparent, parent, sibling),
+            None => self.root = Some(sibling),
+        }
+        self.nodes[sibling].parent = grandparent;
+
+        self.free.push(leaf);
+        self.free.push(parent);
+
+        self.refit(grandparent);
+    }
+
+    fn query_aabb(&self, aabb: &Aabb) -> Vec<Entity> {
+        self.traverse(
+            |node| node.is_overlapping(aabb),
+            |leaf| leaf.is_overlapping(aabb),
+        )
+    }
+
+    /// Entities whose `Aabb` is touched by the `ray` segment.
+    fn query_ray(&self, ray: &Ray) -> Vec<Entity> {
+        self.traverse(
+            |node| ray.overlaps_aabb(node),
+            |leaf| ray.overlaps_aabb(leaf),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::collision::{ Aabb, AabbTree, Broadphase, Ray };
+    use bevy::prelude::{ Entity, Vec2 };
+
+    #[test]
+    fn test_insert_remove() {
+        let mut tree = AabbTree::new(1.0);
+        for i in 0..16 {
+            tree.insert(Entity::from_raw(i), Aabb::new(Vec2::splat(5.0), Vec2::new(i as f32 * 10.0, 0.0)));
+        }
+
+        let mut hits = tree.query_aabb(&Aabb::new(Vec2::splat(1.0), Vec2::new(45.0, 0.0)));
+        hits.sort();
+        assert_eq!(hits, vec![Entity::from_raw(4), Entity::from_raw(5)]);
+
+        tree.remove(Entity::from_raw(5));
+        tree.insert(Entity::from_raw(4), Aabb::new(Vec2::splat(5.0), Vec2::new(100.0, 100.0)));
+        assert!(tree.query_aabb(&Aabb::new(Vec2::splat(1.0), Vec2::new(45.0, 0.0))).is_empty());
+        assert_eq!(tree.query_aabb(&Aabb::new(Vec2::splat(1.0), Vec2::new(100.0, 100.0))), vec![Entity::from_raw(4)]);
+    }
+
+    #[test]
+    fn test_query_ray() {
+        let mut tree = AabbTree::new(1.0);
+        tree.insert(Entity::from_raw(0), Aabb::new(Vec2::splat(5.0), Vec2::new(0.0, -5.0)));
+        tree.insert(Entity::from_raw(1), Aabb::new(Vec2::splat(5.0), Vec2::new(10.0, -5.0)));
+
+        // a ray running down the shared edge of both boxes
+        let ray = Ray::new(Vec2::new(0.0, -4.0), Vec2::new(5.0, 2.0));
+        let mut hits = tree.query_ray(&ray);
+        hits.sort();
+
+        assert_eq!(hits, vec![Entity::from_raw(0), Entity::from_raw(1)]);
+    }
+}
@@ -0,0 +1,121 @@
+use bevy::prelude::*;
+use bevy::utils::HashSet;
+use crate::collision::{ Collider, SpatialIndex };
+use crate::components::*;
+
+pub struct TriggerEntered {
+    pub sensor: Entity,
+    pub entity: Entity,
+}
+
+pub struct TriggerExited {
+    pub sensor: Entity,
+    pub entity: Entity,
+}
+
+/// Pairs of `(sensor, entity)` overlapping as of the last `triggers` run.
+#[derive(Default)]
+pub struct Triggers {
+    pairs: HashSet<(Entity, Entity)>,
+}
+
+impl Triggers {
+    pub fn is_overlapping(&self, sensor: Entity, entity: Entity) -> bool {
+        self.pairs.contains(&(sensor, entity))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &(Entity, Entity)> {
+        self.pairs.iter()
+    }
+}
+
+pub fn triggers(
+    bodies: Query<(Option<&KinematicBody>, Option<&StaticBody>, &GlobalTransform)>,
+    sensors: Query<(Entity, Option<&CollisionLayers>), With<Sensor>>,
+    others: Query<Option<&CollisionLayers>, Without<Sensor>>,
+    index: Res<SpatialIndex>,
+
+    mut triggers: ResMut<Triggers>,
+    mut ev_entered: EventWriter<TriggerEntered>,
+    mut ev_exited: EventWriter<TriggerExited>,
+) {
+    let body_collider = |entity: Entity| -> Option<Collider> {
+        match bodies.get(entity) {
+            Ok((Some(body), _, trans)) => Some(Collider::from_shape(&body.shape, trans, body.rotation_mode)),
+            Ok((_, Some(body), trans)) => Some(Collider::from_shape(&body.shape, trans, body.rotation_mode)),
+            _ => None,
+        }
+    };
+
+    let mut pairs = HashSet::default();
+    for (a_ent, a_layers) in sensors.iter() {
+        let a_col = match body_collider(a_ent) {
+            Some(collider) => collider,
+            None => continue,
+        };
+        let a_layers = a_layers.copied().unwrap_or_default();
+
+        for b_ent in index.query_aabb(&a_col.aabb()) {
+            // other sensors are not part of this query
+            let b_layers = match others.get(b_ent) {
+                Ok(layers) => layers.copied().unwrap_or_default(),
+                Err(_) => continue,
+            };
+            if !a_layers.interacts_with(&b_layers) { continue; }
+
+            // kinematics are indexed with their swept box
+            match body_collider(b_ent) {
+                Some(b_col) if a_col.is_overlapping(&b_col) => pairs.insert((a_ent, b_ent)),
+                _ => continue,
+            };
+        }
+    }
+
+    for &(sensor, entity) in pairs.difference(&triggers.pairs) {
+        ev_entered.send(TriggerEntered { sensor, entity });
+    }
+
+    for &(sensor, entity) in triggers.pairs.difference(&pairs) {
+        ev_exited.send(TriggerExited { sensor, entity });
+    }
+
+    triggers.pairs = pairs;
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::event::Events;
+    use bevy::ecs::system::SystemState;
+    use bevy::prelude::*;
+    use crate::collision::{ triggers, Aabb, SpatialIndex, TriggerEntered, TriggerExited, Triggers };
+    use crate::components::{ KinematicBody, Rectangle, Sensor, StaticBody };
+
+    #[test]
+    fn test_enter_exit() {
+        let mut world = World::new();
+        world.init_resource::<SpatialIndex>();
+        world.init_resource::<Triggers>();
+        world.init_resource::<Events<TriggerEntered>>();
+        world.init_resource::<Events<TriggerExited>>();
+
+        let square = Rectangle::new().with_size(Vec2::splat(2.0));
+        let sensor = world.spawn().insert(StaticBody::new(square)).insert(Sensor).insert(GlobalTransform::identity()).id();
+        let body = world.spawn().insert(KinematicBody::new(square)).id();
+        world.resource_mut::<SpatialIndex>().insert(sensor, Aabb::new(Vec2::splat(1.0), Vec2::ZERO));
+
+        let mut stage = SystemStage::single_threaded().with_system(triggers);
+        let mut events: SystemState<(EventReader<TriggerEntered>, EventReader<TriggerExited>)> = SystemState::new(&mut world);
+
+        // the body walks into the sensor, stays in it for a frame, then leaves
+        for (x, entered, exited) in [(5.0, 0, 0), (1.0, 1, 0), (0.5, 0, 0), (5.0, 0, 1), (6.0, 0, 0)] {
+            world.entity_mut(body).insert(GlobalTransform::from_xyz(x, 0.0, 0.0));
+            world.resource_mut::<SpatialIndex>().insert(body, Aabb::new(Vec2::splat(1.0), Vec2::new(x, 0.0)));
+            world.resource_mut::<SpatialIndex>().update();
+            stage.run(&mut world);
+
+            let (mut ev_entered, mut ev_exited) = events.get_mut(&mut world);
+            assert_eq!(ev_entered.iter().map(|ev| (ev.sensor, ev.entity)).collect::<Vec<_>>(), vec![(sensor, body); entered]);
+            assert_eq!(ev_exited.iter().map(|ev| (ev.sensor, ev.entity)).collect::<Vec<_>>(), vec![(sensor, body); exited]);
+        }
+    }
+}
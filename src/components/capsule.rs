@@ -0,0 +1,79 @@
+use bevy::prelude::Vec2;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CapsuleAxis {
+    #[default]
+    Vertical,
+    Horizontal,
+}
+
+/// Rectangle with fully rounded ends, `height` long along its axis
+/// and `radius * 2` wide across it.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Capsule {
+    radius: f32,
+    height: f32,
+    axis: CapsuleAxis,
+}
+
+impl Capsule {
+    pub fn new() -> Self {
+        Self {
+            radius: 0.0,
+            height: 0.0,
+            axis: CapsuleAxis::default(),
+        }
+    }
+
+    pub fn with_radius(mut self, radius: f32) -> Self {
+        self.radius = radius.abs();
+        self
+    }
+
+    pub fn with_height(mut self, height: f32) -> Self {
+        self.height = height.abs();
+        self
+    }
+
+    pub fn with_axis(mut self, axis: CapsuleAxis) -> Self {
+        self.axis = axis;
+        self
+    }
+
+    pub fn radius(&self) -> f32 {
+        self.radius
+    }
+
+    pub fn height(&self) -> f32 {
+        self.height.max(self.radius * 2.0)
+    }
+
+    pub fn axis(&self) -> CapsuleAxis {
+        self.axis
+    }
+
+    /// Half of the straight segment between the centers of both ends.
+    pub fn half_segment(&self) -> Vec2 {
+        let half = self.height() / 2.0 - self.radius;
+
+        match self.axis {
+            CapsuleAxis::Vertical => Vec2::new(0.0, half),
+            CapsuleAxis::Horizontal => Vec2::new(half, 0.0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::components::{ Capsule, CapsuleAxis };
+    use bevy::math::Vec2;
+
+    #[test]
+    fn test_half_segment() {
+        let a = Capsule::new().with_radius(2.0).with_height(10.0);
+        assert_eq!(a.half_segment(), Vec2::new(0.0, 3.0));
+
+        let b = Capsule::new().with_radius(2.0).with_height(1.0).with_axis(CapsuleAxis::Horizontal);
+        assert_eq!(b.half_segment(), Vec2::ZERO);
+    }
+}
@@ -0,0 +1,21 @@
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Circle {
+    radius: f32,
+}
+
+impl Circle {
+    pub fn new() -> Self {
+        Self {
+            radius: 0.0,
+        }
+    }
+
+    pub fn with_radius(mut self, radius: f32) -> Self {
+        self.radius = radius.abs();
+        self
+    }
+
+    pub fn radius(&self) -> f32 {
+        self.radius
+    }
+}
@@ -0,0 +1,55 @@
+use bevy::prelude::Vec2;
+use crate::components::Shape;
+
+/// Several shapes moving as one body, each placed at an offset from the
+/// body position.
+///
+/// `Contact`s report which of them was touched by its index.
+#[derive(Clone, Debug, Default)]
+pub struct Compound {
+    shapes: Vec<(Vec2, Shape)>,
+}
+
+impl Compound {
+    pub fn new() -> Self {
+        Self {
+            shapes: Vec::new(),
+        }
+    }
+
+    pub fn with_shape(mut self, offset: Vec2, shape: impl Into<Shape>) -> Self {
+        self.shapes.push((offset, shape.into()));
+        self
+    }
+
+    pub fn shapes(&self) -> &[(Vec2, Shape)] {
+        &self.shapes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::collision::{ Aabb, Collider };
+    use crate::components::{ Compound, Rectangle, RotationMode, Shape };
+    use bevy::prelude::{ GlobalTransform, Vec2 };
+
+    #[test]
+    fn test_sweep_parts() {
+        // an L made of a floor and a wall on its right
+        let shape = Shape::from(Compound::new()
+            .with_shape(Vec2::ZERO, Rectangle::new().with_size(Vec2::new(10.0, 2.0)))
+            .with_shape(Vec2::new(4.0, 5.0), Rectangle::new().with_size(Vec2::new(2.0, 8.0))));
+        let a = Collider::from_shape(&shape, &GlobalTransform::identity(), RotationMode::Exact);
+        assert_eq!(a.aabb().min(), Vec2::new(-5.0, -1.0));
+        assert_eq!(a.aabb().max(), Vec2::new(5.0, 9.0));
+
+        let b = Collider::Aabb(Aabb::new(Vec2::splat(1.0), Vec2::new(0.0, 5.0)));
+        let (hit, a_part, b_part) = b.sweep_parts(&a, Vec2::new(4.0, 0.0)).unwrap();
+        assert_eq!(hit.normal, Vec2::new(-1.0, 0.0));
+        assert_eq!((a_part, b_part), (0, 1));
+
+        let (hit, a_part, b_part) = a.sweep_parts(&b, Vec2::new(0.0, 4.0)).unwrap();
+        assert_eq!(hit.normal, Vec2::new(0.0, -1.0));
+        assert_eq!((a_part, b_part), (0, 0));
+    }
+}
@@ -0,0 +1,134 @@
+use bevy::prelude::Component;
+
+/// Named layers that can be turned into `CollisionLayers` bits.
+///
+/// ```
+/// use bevy_simple_aabb::prelude::*;
+///
+/// #[derive(Clone, Copy)]
+/// enum Layer { Ground, Player, Enemy }
+///
+/// impl PhysicsLayer for Layer {
+///     fn to_bits(&self) -> u32 {
+///         1 << (*self as u32)
+///     }
+/// }
+///
+/// let enemy = CollisionLayers::none()
+///     .with_membership(Layer::Enemy)
+///     .with_filter(Layer::Ground)
+///     .with_filter(Layer::Player);
+/// ```
+pub trait PhysicsLayer {
+    fn to_bits(&self) -> u32;
+}
+
+impl PhysicsLayer for u32 {
+    fn to_bits(&self) -> u32 {
+        *self
+    }
+}
+
+/// Which layers a body or a ray belongs to and which ones it collides with.
+///
+/// Two bodies only interact when each one is a member of a layer
+/// the other one filters for, while rays and queries see the bodies
+/// that are members of a layer they filter for. Entities without this
+/// component are members of every layer and collide with every layer.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CollisionLayers {
+    memberships: u32,
+    filters: u32,
+}
+
+impl Default for CollisionLayers {
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
+impl CollisionLayers {
+    pub fn new(memberships: impl PhysicsLayer, filters: impl PhysicsLayer) -> Self {
+        Self {
+            memberships: memberships.to_bits(),
+            filters: filters.to_bits(),
+        }
+    }
+
+    pub fn all() -> Self {
+        Self::new(u32::MAX, u32::MAX)
+    }
+
+    pub fn none() -> Self {
+        Self::new(0, 0)
+    }
+
+    pub fn with_membership(mut self, layer: impl PhysicsLayer) -> Self {
+        self.memberships |= layer.to_bits();
+        self
+    }
+
+    pub fn without_membership(mut self, layer: impl PhysicsLayer) -> Self {
+        self.memberships &= !layer.to_bits();
+        self
+    }
+
+    pub fn with_filter(mut self, layer: impl PhysicsLayer) -> Self {
+        self.filters |= layer.to_bits();
+        self
+    }
+
+    pub fn without_filter(mut self, layer: impl PhysicsLayer) -> Self {
+        self.filters &= !layer.to_bits();
+        self
+    }
+
+    pub fn memberships(&self) -> u32 {
+        self.memberships
+    }
+
+    pub fn filters(&self) -> u32 {
+        self.filters
+    }
+
+    /// Whether `other` is a member of a layer this filters for.
+    pub fn filters_for(&self, other: &CollisionLayers) -> bool {
+        self.filters & other.memberships != 0
+    }
+
+    pub fn interacts_with(&self, other: &CollisionLayers) -> bool {
+        self.filters_for(other) && other.filters_for(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::components::{ CollisionLayers, PhysicsLayer };
+
+    #[derive(Clone, Copy)]
+    enum Layer {
+        Wall,
+        Enemy,
+    }
+
+    impl PhysicsLayer for Layer {
+        fn to_bits(&self) -> u32 {
+            1 << (*self as u32)
+        }
+    }
+
+    #[test]
+    fn test_interacts() {
+        let wall = CollisionLayers::none().with_membership(Layer::Wall).with_filter(Layer::Enemy);
+        let enemy = CollisionLayers::none().with_membership(Layer::Enemy).with_filter(Layer::Wall);
+
+        assert!(wall.interacts_with(&enemy));
+        assert!(!enemy.interacts_with(&enemy));
+        assert!(CollisionLayers::default().interacts_with(&enemy));
+
+        // a ray that belongs to nothing still sees walls
+        let ray = CollisionLayers::none().with_filter(Layer::Wall);
+        assert!(ray.filters_for(&wall));
+        assert!(!ray.filters_for(&enemy));
+    }
+}
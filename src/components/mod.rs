@@ -1,29 +1,64 @@
+pub mod capsule;
+pub mod circle;
+pub mod compound;
+pub mod layers;
+pub mod oriented_box;
+pub mod polygon;
 pub mod raycast;
 pub mod rectangle;
+pub mod shape;
+pub mod shapecast;
+pub mod slope;
 
 use bevy::prelude::{ Component, Vec2 };
 use crate::collision::Contact;
+pub use capsule::*;
+pub use circle::*;
+pub use compound::*;
+pub use layers::*;
+pub use oriented_box::*;
+pub use polygon::*;
 pub use raycast::*;
 pub use rectangle::*;
+pub use shape::*;
+pub use shapecast::*;
+pub use slope::*;
 
 #[derive(Component, Default)]
 pub struct KinematicBody {
-    pub shape: Rectangle,
+    pub shape: Shape,
     pub motion: Vec2,
+    /// Ignores one-way `StaticBody`s until the body is clear of all of them.
+    pub drop_through: bool,
+    pub rotation_mode: RotationMode,
 
+    pub(crate) carried: Vec2,
     pub(crate) contacts: Vec<Contact>,
 }
 
 impl KinematicBody {
-    pub fn new(shape: Rectangle) -> Self {
+    pub fn new(shape: impl Into<Shape>) -> Self {
         Self {
-            shape,
+            shape: shape.into(),
             motion: Vec2::ZERO,
+            drop_through: false,
+            rotation_mode: RotationMode::default(),
 
+            carried: Vec2::ZERO,
             contacts: Vec::new(),
         }
     }
 
+    pub fn with_rotation_mode(mut self, rotation_mode: RotationMode) -> Self {
+        self.rotation_mode = rotation_mode;
+        self
+    }
+
+    /// `motion` plus how far moving platforms carry or push the body this frame.
+    pub fn total_motion(&self) -> Vec2 {
+        self.motion + self.carried
+    }
+
     pub fn get_contacts(&self) -> Vec<Contact> {
         self.contacts.clone()
     }
@@ -31,13 +66,53 @@ impl KinematicBody {
 
 #[derive(Component, Default)]
 pub struct StaticBody {
-    pub shape: Rectangle,
+    pub shape: Shape,
+    /// Moves the body every frame in world space, even under a parent,
+    /// pushing and carrying kinematics on the way.
+    pub motion: Vec2,
+    /// Only blocks bodies hitting it with a contact normal pointing this way.
+    pub one_way: Option<Vec2>,
+    pub rotation_mode: RotationMode,
 }
 
 impl StaticBody {
-    pub fn new(shape: Rectangle) -> Self {
+    pub fn new(shape: impl Into<Shape>) -> Self {
         Self {
-            shape,
+            shape: shape.into(),
+            motion: Vec2::ZERO,
+            one_way: None,
+            rotation_mode: RotationMode::default(),
         }
     }
-}
\ No newline at end of file
+
+    pub fn with_one_way(mut self, direction: Vec2) -> Self {
+        self.one_way = Some(direction.normalize_or_zero());
+        self
+    }
+
+    pub fn with_rotation_mode(mut self, rotation_mode: RotationMode) -> Self {
+        self.rotation_mode = rotation_mode;
+        self
+    }
+}
+
+/// How the rotation of the `GlobalTransform` applies to the shape of a body.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RotationMode {
+    /// The shape stays axis aligned.
+    Ignore,
+    /// Rotated rectangles, slopes, polygons and capsules are replaced by the
+    /// `Aabb` enclosing them, keeping the cheaper box tests at the cost of
+    /// precision. Circles and oriented boxes keep their exact shape.
+    #[default]
+    Enclose,
+    /// The shape rotates with the transform, rectangles becoming polygons.
+    Exact,
+}
+
+/// Marks a `StaticBody` or `KinematicBody` as a trigger area.
+///
+/// Sensors never block or get blocked by other bodies, they only
+/// report overlaps through `TriggerEntered` and `TriggerExited`.
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct Sensor;
\ No newline at end of file
@@ -0,0 +1,41 @@
+use bevy::prelude::Vec2;
+
+/// Box turned by `angle` radians counter clockwise, always following the
+/// rotation of the body transform too unless it uses `RotationMode::Ignore`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct OrientedBox {
+    size: Vec2,
+    angle: f32,
+}
+
+impl OrientedBox {
+    pub fn new() -> Self {
+        Self {
+            size: Vec2::ZERO,
+            angle: 0.0,
+        }
+    }
+
+    pub fn with_size(mut self, size: Vec2) -> Self {
+        self.size = size.max(-size);
+        self
+    }
+
+    pub fn with_angle(mut self, angle: f32) -> Self {
+        self.angle = angle;
+        self
+    }
+
+    pub fn size(&self) -> Vec2 {
+        self.size
+    }
+
+    pub fn angle(&self) -> f32 {
+        self.angle
+    }
+
+    /// Direction of the local x axis of the box.
+    pub fn axis(&self) -> Vec2 {
+        Vec2::new(self.angle.cos(), self.angle.sin())
+    }
+}
@@ -0,0 +1,68 @@
+use bevy::prelude::Vec2;
+use crate::collision::Polygon;
+
+/// Convex polygon with vertices relative to the body position.
+///
+/// Vertices are kept as the counter clockwise convex hull of the given
+/// points, so concave input is filled in. A polygon without area, like the
+/// default one, has no vertices and collides with nothing.
+#[derive(Clone, Debug, Default)]
+pub struct ConvexPolygon {
+    vertices: Vec<Vec2>,
+}
+
+impl ConvexPolygon {
+    pub fn new() -> Self {
+        Self {
+            vertices: Vec::new(),
+        }
+    }
+
+    /// Polygon with `sides` vertices evenly spread on a circle of `radius`,
+    /// the first one pointing right.
+    pub fn regular(sides: usize, radius: f32) -> Self {
+        let step = std::f32::consts::TAU / sides as f32;
+
+        Self::new().with_vertices(
+            (0..sides).map(|i| Vec2::new((step * i as f32).cos(), (step * i as f32).sin()) * radius.abs()).collect()
+        )
+    }
+
+    /// Sets the vertices to the hull of `vertices`, or to none if
+    /// they are fewer than 3 or all on one line.
+    pub fn with_vertices(mut self, vertices: Vec<Vec2>) -> Self {
+        let hull = Polygon::convex_hull(vertices);
+        self.vertices = match hull.vertices().len() {
+            0..=2 => Vec::new(),
+            _ => hull.vertices().to_vec(),
+        };
+        self
+    }
+
+    pub fn vertices(&self) -> &[Vec2] {
+        &self.vertices
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::components::ConvexPolygon;
+    use bevy::math::Vec2;
+
+    #[test]
+    fn test_with_vertices() {
+        let a = ConvexPolygon::new().with_vertices(vec![
+            Vec2::new(0.0, 2.0),
+            Vec2::new(-2.0, -2.0),
+            Vec2::new(0.0, 0.0),
+            Vec2::new(2.0, -2.0),
+        ]);
+
+        assert_eq!(a.vertices(), [Vec2::new(-2.0, -2.0), Vec2::new(2.0, -2.0), Vec2::new(0.0, 2.0)]);
+        assert_eq!(ConvexPolygon::regular(6, 2.0).vertices().len(), 6);
+
+        // degenerate input leaves the polygon empty
+        assert!(ConvexPolygon::regular(2, 2.0).vertices().is_empty());
+        assert!(ConvexPolygon::new().with_vertices(vec![Vec2::ZERO, Vec2::ONE, Vec2::splat(2.0)]).vertices().is_empty());
+    }
+}
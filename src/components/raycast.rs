@@ -1,5 +1,5 @@
 use bevy::{prelude::{ Bundle, Component, Entity, Vec2 }, transform::TransformBundle};
-use crate::collision::ray::Hit;
+use crate::collision::RayHit;
 
 #[derive(Bundle, Default)]
 pub struct RaycastBundle {
@@ -13,8 +13,16 @@ pub struct RaycastBundle {
 pub struct Raycast {
     pub direction: Vec2,
     pub offset: Vec2,
+    /// Lets the ray hit the entity it's attached to and its ancestors,
+    /// which are skipped by default.
+    pub hit_ancestors: bool,
+    /// When not empty, only these entities can be hit.
+    pub include: Vec<Entity>,
+    pub exclude: Vec<Entity>,
+    /// Only keeps the closest hit instead of every one.
+    pub stop_at_first: bool,
 
-    pub(crate) hits: Vec<(Entity, Hit)>,
+    pub(crate) hits: Vec<RayHit>,
 }
 
 impl Raycast {
@@ -32,10 +40,35 @@ impl Raycast {
         self
     }
 
-    pub fn get_hits(&self) -> Vec<(Entity, Hit)> {
+    pub fn with_hit_ancestors(mut self, hit_ancestors: bool) -> Self {
+        self.hit_ancestors = hit_ancestors;
+        self
+    }
+
+    pub fn with_included(mut self, entity: Entity) -> Self {
+        self.include.push(entity);
+        self
+    }
+
+    pub fn with_excluded(mut self, entity: Entity) -> Self {
+        self.exclude.push(entity);
+        self
+    }
+
+    pub fn with_stop_at_first(mut self, stop_at_first: bool) -> Self {
+        self.stop_at_first = stop_at_first;
+        self
+    }
+
+    /// Hits sorted by time, the closest one first.
+    pub fn get_hits(&self) -> Vec<RayHit> {
         self.hits.clone()
     }
 
+    pub fn first_hit(&self) -> Option<RayHit> {
+        self.hits.first().copied()
+    }
+
     pub fn is_colliding(&self) -> bool {
         self.hits.len() > 0
     }
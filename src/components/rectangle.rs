@@ -3,12 +3,16 @@ use bevy::prelude::{ Component, Vec2 };
 #[derive(Component, Clone, Copy, Debug, Default)]
 pub struct Rectangle {
     size: Vec2,
+    offset: Vec2,
+    anchor: Vec2,
 }
 
 impl Rectangle {
     pub fn new() -> Self {
         Self {
             size: Vec2::ZERO,
+            offset: Vec2::ZERO,
+            anchor: Vec2::ZERO,
         }
     }
 
@@ -17,9 +21,34 @@ impl Rectangle {
         self
     }
 
+    pub fn with_offset(mut self, offset: Vec2) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Point of the rectangle placed at the transform, from `(-0.5, -0.5)`
+    /// at the bottom left corner to `(0.5, 0.5)` at the top right one.
+    pub fn with_anchor(mut self, anchor: Vec2) -> Self {
+        self.anchor = anchor;
+        self
+    }
+
     pub fn size(&self) -> Vec2 {
         self.size
     }
+
+    pub fn offset(&self) -> Vec2 {
+        self.offset
+    }
+
+    pub fn anchor(&self) -> Vec2 {
+        self.anchor
+    }
+
+    /// Center of the rectangle relative to the transform.
+    pub fn center(&self) -> Vec2 {
+        self.offset - self.anchor * self.size
+    }
 }
 
 
@@ -36,4 +65,13 @@ mod tests {
         let b = Rectangle::new().with_size(Vec2::new(-4.0, -6.0));
         assert_eq!(b.size, Vec2::new(4.0, 6.0));
     }
+
+    #[test]
+    fn test_center() {
+        let a = Rectangle::new().with_size(Vec2::new(2.0, 4.0)).with_anchor(Vec2::new(0.0, -0.5));
+        assert_eq!(a.center(), Vec2::new(0.0, 2.0));
+
+        let b = a.with_offset(Vec2::new(1.0, -1.0));
+        assert_eq!(b.center(), Vec2::new(1.0, 1.0));
+    }
 }
\ No newline at end of file
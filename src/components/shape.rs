@@ -0,0 +1,61 @@
+use crate::components::{ Capsule, Circle, Compound, ConvexPolygon, OrientedBox, Rectangle, Slope };
+
+/// Any of the shapes a body can use.
+#[derive(Clone, Debug)]
+pub enum Shape {
+    Rectangle(Rectangle),
+    Slope(Slope),
+    Circle(Circle),
+    Capsule(Capsule),
+    Polygon(ConvexPolygon),
+    OrientedBox(OrientedBox),
+    Compound(Compound),
+}
+
+impl Default for Shape {
+    fn default() -> Self {
+        Self::Rectangle(Rectangle::default())
+    }
+}
+
+impl From<Rectangle> for Shape {
+    fn from(rectangle: Rectangle) -> Self {
+        Self::Rectangle(rectangle)
+    }
+}
+
+impl From<Slope> for Shape {
+    fn from(slope: Slope) -> Self {
+        Self::Slope(slope)
+    }
+}
+
+impl From<Circle> for Shape {
+    fn from(circle: Circle) -> Self {
+        Self::Circle(circle)
+    }
+}
+
+impl From<Capsule> for Shape {
+    fn from(capsule: Capsule) -> Self {
+        Self::Capsule(capsule)
+    }
+}
+
+impl From<ConvexPolygon> for Shape {
+    fn from(polygon: ConvexPolygon) -> Self {
+        Self::Polygon(polygon)
+    }
+}
+
+impl From<Compound> for Shape {
+    fn from(compound: Compound) -> Self {
+        Self::Compound(compound)
+    }
+}
+
+impl From<OrientedBox> for Shape {
+    fn from(oriented_box: OrientedBox) -> Self {
+        Self::OrientedBox(oriented_box)
+    }
+}
\ No newline at end of file
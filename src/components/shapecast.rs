@@ -0,0 +1,83 @@
+use bevy::{prelude::{ Bundle, Component, Entity, Vec2 }, transform::TransformBundle};
+use crate::{ collision::ShapeHit, components::Shape };
+
+#[derive(Bundle, Default)]
+pub struct ShapeCastBundle {
+    pub shape_cast: ShapeCast,
+
+    #[bundle]
+    pub transform_bundle: TransformBundle,
+}
+
+/// Like a `Raycast`, sweeping `shape` by `direction` instead of a point.
+///
+/// Useful to check whether a hitbox fits somewhere before moving it there,
+/// a cast starting inside a body hits it at time 0.
+#[derive(Component, Default)]
+pub struct ShapeCast {
+    pub shape: Shape,
+    pub direction: Vec2,
+    pub offset: Vec2,
+    /// Lets the cast hit the entity it's attached to and its ancestors,
+    /// which are skipped by default.
+    pub hit_ancestors: bool,
+    /// When not empty, only these entities can be hit.
+    pub include: Vec<Entity>,
+    pub exclude: Vec<Entity>,
+    /// Only keeps the closest hit instead of every one.
+    pub stop_at_first: bool,
+
+    pub(crate) hits: Vec<ShapeHit>,
+}
+
+impl ShapeCast {
+    pub fn new(shape: impl Into<Shape>) -> Self {
+        Self {
+            shape: shape.into(),
+            ..Self::default()
+        }
+    }
+
+    pub fn with_direction(mut self, direction: Vec2) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    pub fn with_offset(mut self, offset: Vec2) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    pub fn with_hit_ancestors(mut self, hit_ancestors: bool) -> Self {
+        self.hit_ancestors = hit_ancestors;
+        self
+    }
+
+    pub fn with_included(mut self, entity: Entity) -> Self {
+        self.include.push(entity);
+        self
+    }
+
+    pub fn with_excluded(mut self, entity: Entity) -> Self {
+        self.exclude.push(entity);
+        self
+    }
+
+    pub fn with_stop_at_first(mut self, stop_at_first: bool) -> Self {
+        self.stop_at_first = stop_at_first;
+        self
+    }
+
+    /// Hits sorted by time, the closest one first.
+    pub fn get_hits(&self) -> Vec<ShapeHit> {
+        self.hits.clone()
+    }
+
+    pub fn first_hit(&self) -> Option<ShapeHit> {
+        self.hits.first().copied()
+    }
+
+    pub fn is_colliding(&self) -> bool {
+        !self.hits.is_empty()
+    }
+}
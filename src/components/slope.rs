@@ -0,0 +1,84 @@
+use bevy::prelude::Vec2;
+
+/// Which way the sloped face of a `Slope` points.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SlopeFacing {
+    /// Floor rising to the right.
+    #[default]
+    UpLeft,
+    /// Floor rising to the left.
+    UpRight,
+    /// Ceiling going down to the right.
+    DownLeft,
+    /// Ceiling going down to the left.
+    DownRight,
+}
+
+impl SlopeFacing {
+    pub fn sign(&self) -> Vec2 {
+        match self {
+            Self::UpLeft => Vec2::new(-1.0, 1.0),
+            Self::UpRight => Vec2::new(1.0, 1.0),
+            Self::DownLeft => Vec2::new(-1.0, -1.0),
+            Self::DownRight => Vec2::new(1.0, -1.0),
+        }
+    }
+}
+
+/// Right triangle filling half of a `size.x` (run) by `size.y` (rise) box.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Slope {
+    size: Vec2,
+    facing: SlopeFacing,
+}
+
+impl Slope {
+    pub fn new() -> Self {
+        Self {
+            size: Vec2::ZERO,
+            facing: SlopeFacing::default(),
+        }
+    }
+
+    pub fn with_size(mut self, size: Vec2) -> Self {
+        self.size = size.max(-size);
+        self
+    }
+
+    pub fn with_facing(mut self, facing: SlopeFacing) -> Self {
+        self.facing = facing;
+        self
+    }
+
+    pub fn size(&self) -> Vec2 {
+        self.size
+    }
+
+    pub fn facing(&self) -> SlopeFacing {
+        self.facing
+    }
+
+    /// Vertices relative to the center of the box, right angle first.
+    pub fn vertices(&self) -> [Vec2; 3] {
+        let corner = -self.facing.sign() * self.size / 2.0;
+
+        [
+            corner,
+            Vec2::new(-corner.x, corner.y),
+            Vec2::new(corner.x, -corner.y),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::components::{ Slope, SlopeFacing };
+    use bevy::math::Vec2;
+
+    #[test]
+    fn test_vertices() {
+        let a = Slope::new().with_size(Vec2::new(4.0, 2.0)).with_facing(SlopeFacing::UpLeft);
+
+        assert_eq!(a.vertices(), [Vec2::new(2.0, -1.0), Vec2::new(-2.0, -1.0), Vec2::new(2.0, 1.0)]);
+    }
+}
@@ -4,6 +4,8 @@ pub mod plugin;
 pub mod utils;
 
 pub mod prelude {
-    pub use crate::components::{ KinematicBody, StaticBody, Raycast, RaycastBundle, Rectangle };
+    pub use crate::components::{ Capsule, CapsuleAxis, Circle, CollisionLayers, Compound, ConvexPolygon, KinematicBody, OrientedBox, PhysicsLayer, RotationMode, Sensor, StaticBody, Raycast, RaycastBundle, Rectangle, Shape, ShapeCast, ShapeCastBundle, Slope, SlopeFacing };
+    pub use crate::collision::{ AabbTree, Broadphase, BruteForce, Movement, QueryFilter, RayHit, ShapeHit, SolverSettings, SpatialGrid, SpatialIndex, SpatialQuery, SweepAndPrune };
+    pub use crate::collision::{ CollisionEnded, CollisionStarted, TriggerEntered, TriggerExited };
     pub use crate::plugin::PhysicsPlugin;
 }
\ No newline at end of file
@@ -12,17 +12,32 @@ impl Plugin for PhysicsPlugin {
             .add_event::<BroadEvent>()
             .add_event::<NarrowEvent>()
             .add_event::<MoveEvent>()
+            .add_event::<TriggerEntered>()
+            .add_event::<TriggerExited>()
+            .add_event::<CollisionStarted>()
+            .add_event::<CollisionEnded>()
+
+            .init_resource::<SpatialIndex>()
+            .init_resource::<SolverSettings>()
+            .init_resource::<Triggers>()
+            .init_resource::<ContactPairs>()
             
             .add_stage_after(CoreStage::Update, PhysicsStage, SystemStage::single_threaded())
             
+            .add_system_to_stage(PhysicsStage, carry_riders.before(update_index))
+            .add_system_to_stage(PhysicsStage, move_platforms.after(carry_riders).before(update_index))
+            .add_system_to_stage(PhysicsStage, update_index.before(broadphase))
             .add_system_to_stage(PhysicsStage, broadphase)
             .add_system_to_stage(PhysicsStage, narrowphase.after(broadphase))
             
-            .add_system_to_stage(PhysicsStage, clear_contacts.before(solve))
+            .add_system_to_stage(PhysicsStage, clear_contacts.after(carry_riders).before(solve))
             .add_system_to_stage(PhysicsStage, solve.after(narrowphase))
             .add_system_to_stage(PhysicsStage, move_entities.after(solve))
+            .add_system_to_stage(PhysicsStage, contact_events.after(solve))
 
-            .add_system_to_stage(PhysicsStage, raycasts.after(move_entities));
+            .add_system_to_stage(PhysicsStage, triggers.after(update_index))
+            .add_system_to_stage(PhysicsStage, raycasts.after(move_entities))
+            .add_system_to_stage(PhysicsStage, shape_casts.after(move_entities));
 
     }
 }
\ No newline at end of file
@@ -2,6 +2,11 @@ use bevy::prelude::Vec2;
 
 pub const EPSILON: f32 = 0.0000001;
 
-pub fn slide_motion(motion: &mut Vec2, normal: Vec2, time: f32) {
-    *motion += motion.abs() * normal * (1.0 - time - EPSILON)
+/// Stops `motion` against a body moving by `other` after the time of impact,
+/// following it along `normal` when it moves away and stopping otherwise.
+pub fn stop_motion(motion: &mut Vec2, other: Vec2, normal: Vec2, time: f32) {
+    let along = motion.dot(normal);
+    let after = along.max(other.dot(normal).min(0.0));
+
+    *motion += normal * (after - along) * (1.0 - time - EPSILON)
 }
\ No newline at end of file